@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use reqwest::{Client, Request, Response, ResponseBuilderExt};
+use serde::{Deserialize, Serialize};
+
+use crate::api::ApiError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    // Send requests for real and write every exchange to the cassette file.
+    Record,
+    // Answer requests from the cassette file without touching the network.
+    Replay,
+}
+
+// Recorded request/response pairs, stored as JSON. Requests are matched on
+// method and path (query included), and on the body when `match_body` is
+// set; each recorded exchange is replayed once, in order. Bodies are kept as
+// UTF-8 text, after any Content-Encoding has been removed.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    match_body: bool,
+    interactions: Mutex<Vec<Interaction>>,
+    // Held across recording and rewriting the file, so concurrent recordings
+    // cannot write an older snapshot over a newer one.
+    file_lock: tokio::sync::Mutex<()>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    method: String,
+    path: String,
+    request_body: Option<String>,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+    #[serde(skip)]
+    played: bool,
+}
+
+impl Cassette {
+    // Starts an empty cassette at `path`, replacing any existing file once
+    // the first exchange is recorded.
+    pub fn record(path: impl AsRef<Path>) -> Self {
+        Self::new(path.as_ref(), CassetteMode::Record, Vec::new())
+    }
+
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self, ApiError> {
+        let path = path.as_ref();
+        let raw = std::fs::read(path)?;
+        let interactions = serde_json::from_slice(&raw).map_err(|err| ApiError::Cassette {
+            reason: format!("invalid cassette file {}: {err}", path.display()),
+        })?;
+        Ok(Self::new(path, CassetteMode::Replay, interactions))
+    }
+
+    fn new(path: &Path, mode: CassetteMode, interactions: Vec<Interaction>) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            mode,
+            match_body: false,
+            interactions: Mutex::new(interactions),
+            file_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    // Also require the request body to match when replaying.
+    pub fn match_body(mut self, match_body: bool) -> Self {
+        self.match_body = match_body;
+        self
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub(super) async fn execute(&self, http: &Client, request: Request) -> Result<Response, ApiError> {
+        match self.mode {
+            CassetteMode::Replay => self.play(&request),
+            CassetteMode::Record => self.record_exchange(http, request).await,
+        }
+    }
+
+    fn play(&self, request: &Request) -> Result<Response, ApiError> {
+        let method = request.method().as_str();
+        let path = path_and_query(request);
+        let request_body = body_text(request);
+
+        let mut interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+        let found = interactions.iter_mut().find(|i| {
+            !i.played
+                && i.method == method
+                && i.path == path
+                && (!self.match_body || i.request_body == request_body)
+        });
+        let Some(interaction) = found else {
+            return Err(ApiError::Cassette {
+                reason: format!("no recorded response for {method} {path}"),
+            });
+        };
+
+        interaction.played = true;
+        let body = Bytes::from(interaction.body.clone());
+        rebuild(request.url().clone(), interaction, body)
+    }
+
+    async fn record_exchange(&self, http: &Client, request: Request) -> Result<Response, ApiError> {
+        let method = request.method().to_string();
+        let path = path_and_query(&request);
+        let request_body = body_text(&request);
+
+        let resp = http.execute(request).await?;
+        let status = resp.status().as_u16();
+        let url = resp.url().clone();
+        let headers = resp
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = resp.bytes().await?;
+
+        let interaction = Interaction {
+            method,
+            path,
+            request_body,
+            status,
+            headers,
+            body: String::from_utf8_lossy(&body).into_owned(),
+            played: false,
+        };
+        let resp = rebuild(url, &interaction, body)?;
+
+        let _file = self.file_lock.lock().await;
+        let file = {
+            let mut interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+            interactions.push(interaction);
+            serde_json::to_vec_pretty(&*interactions).map_err(|err| ApiError::Cassette {
+                reason: format!("cannot serialize cassette: {err}"),
+            })?
+        };
+        tokio::fs::write(&self.path, file).await?;
+
+        Ok(resp)
+    }
+}
+
+fn path_and_query(request: &Request) -> String {
+    let url = request.url();
+    match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    }
+}
+
+fn body_text(request: &Request) -> Option<String> {
+    let body = request.body()?.as_bytes()?;
+    Some(String::from_utf8_lossy(body).into_owned())
+}
+
+fn rebuild(url: reqwest::Url, interaction: &Interaction, body: Bytes) -> Result<Response, ApiError> {
+    let mut builder = http::Response::builder().status(interaction.status).url(url);
+    for (name, value) in &interaction.headers {
+        builder = builder.header(name, value);
+    }
+    let resp = builder.body(body).map_err(|err| ApiError::Cassette {
+        reason: format!("invalid recorded response for {} {}: {err}", interaction.method, interaction.path),
+    })?;
+    Ok(resp.into())
+}
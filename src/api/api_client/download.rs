@@ -0,0 +1,319 @@
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+
+use async_compression::tokio::write::GzipEncoder;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use percent_encoding::percent_decode_str;
+use reqwest::{Method, StatusCode, Url};
+use reqwest::header::{ACCEPT_RANGES, CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_RANGE, RANGE};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+
+use super::ApiClient;
+use crate::api::{ApiError, HeaderPairs};
+
+impl ApiClient {
+    // -----------------------
+    //   PARALLEL DOWNLOAD
+    // -----------------------
+    // Fetches `dest` in `chunk_size` ranges, `concurrency` at a time. Servers
+    // without range support, or that answer a range request with the whole
+    // body, get a single plain download instead. On failure nothing is left
+    // at `dest`.
+    pub async fn download_parallel(
+        &self,
+        path: &str,
+        dest: impl AsRef<Path>,
+        chunk_size: u64,
+        concurrency: usize,
+        extra_headers: Option<&dyn HeaderPairs>,
+    ) -> Result<u64, ApiError> {
+        let dest = dest.as_ref();
+        let req = self.apply_headers(self.request(Method::HEAD, path), extra_headers)?;
+        let head = self.error_for_status(self.send(req).await?)?;
+
+        let supports_ranges = head
+            .headers
+            .get(ACCEPT_RANGES)
+            .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"bytes"));
+        let total_len = head
+            .headers
+            .get(CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+
+        let total_len = match total_len {
+            Some(len) if supports_ranges && len > 0 => len,
+            _ => return self.download_whole(path, dest, extra_headers).await,
+        };
+
+        let ranged = self
+            .download_ranges(path, dest, total_len, chunk_size, concurrency, extra_headers)
+            .await;
+        let result = match ranged {
+            Ok(true) => Ok(total_len),
+            Ok(false) => self.download_whole(path, dest, extra_headers).await,
+            Err(err) => Err(err),
+        };
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(dest).await;
+        }
+        result
+    }
+
+    // false as soon as a range request is answered with the whole body;
+    // the remaining chunks are dropped.
+    async fn download_ranges(
+        &self,
+        path: &str,
+        dest: &Path,
+        total_len: u64,
+        chunk_size: u64,
+        concurrency: usize,
+        extra_headers: Option<&dyn HeaderPairs>,
+    ) -> Result<bool, ApiError> {
+        let file = File::create(dest).await?;
+        file.set_len(total_len).await?;
+        drop(file);
+
+        let chunk_size = chunk_size.max(1);
+        let ranges = (0..total_len)
+            .step_by(chunk_size as usize)
+            .map(|start| (start, (start + chunk_size).min(total_len) - 1));
+
+        let mut chunks = stream::iter(ranges)
+            .map(|(start, end)| self.download_range(path, dest, start, end, total_len, extra_headers))
+            .buffer_unordered(concurrency.max(1));
+        while let Some(written) = chunks.try_next().await? {
+            if !written {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    // false, without writing, when the server ignored the range and sent 200.
+    async fn download_range(
+        &self,
+        path: &str,
+        dest: &Path,
+        start: u64,
+        end: u64,
+        total_len: u64,
+        extra_headers: Option<&dyn HeaderPairs>,
+    ) -> Result<bool, ApiError> {
+        let req = self
+            .request(Method::GET, path)
+            .header(RANGE, format!("bytes={start}-{end}"));
+        let req = self.apply_headers(req, extra_headers)?;
+        let resp = self.error_for_status(self.send(req).await?)?;
+        if resp.status == StatusCode::OK {
+            return Ok(false);
+        }
+
+        let expected_range = format!("bytes {start}-{end}/{total_len}");
+        let content_range = resp.header(CONTENT_RANGE.as_str()).map(str::trim);
+        if resp.status != StatusCode::PARTIAL_CONTENT || content_range != Some(expected_range.as_str()) {
+            return Err(ApiError::RangeMismatch {
+                expected: expected_range,
+                received: content_range.map(str::to_string),
+            });
+        }
+
+        let expected = end - start + 1;
+        let received = resp.body.len() as u64;
+        if received != expected {
+            return Err(ApiError::ContentLengthMismatch { expected, received });
+        }
+
+        let mut file = OpenOptions::new().write(true).open(dest).await?;
+        file.seek(SeekFrom::Start(start)).await?;
+        file.write_all(&resp.body).await?;
+        file.flush().await?;
+
+        Ok(true)
+    }
+
+    async fn download_whole(
+        &self,
+        path: &str,
+        dest: &Path,
+        extra_headers: Option<&dyn HeaderPairs>,
+    ) -> Result<u64, ApiError> {
+        let req = self.apply_headers(self.request(Method::GET, path), extra_headers)?;
+        let _permit = self.acquire_permit().await;
+        let resp = self.send_streaming(req).await?;
+
+        write_body(resp, dest, self.max_decompressed_size).await
+    }
+
+    // -----------------------
+    //    DOWNLOAD TO GZIP
+    // -----------------------
+    // Streams the body (after any transfer decompression) into a gzip file at
+    // `dest`, for archiving responses. Returns the uncompressed byte count.
+    pub async fn download_to_gzip(
+        &self,
+        path: &str,
+        dest: impl AsRef<Path>,
+        extra_headers: Option<&dyn HeaderPairs>,
+    ) -> Result<u64, ApiError> {
+        let req = self.apply_headers(self.request(Method::GET, path), extra_headers)?;
+        let _permit = self.acquire_permit().await;
+        let resp = self.send_streaming(req).await?;
+
+        let mut gzip = GzipEncoder::new(File::create(dest.as_ref()).await?);
+        let written = copy_body(resp, &mut gzip, self.max_decompressed_size).await?;
+        // shutdown writes the gzip trailer and flushes the file.
+        gzip.shutdown().await?;
+
+        Ok(written)
+    }
+
+    // -----------------------
+    //  DOWNLOAD SUGGESTED NAME
+    // -----------------------
+    // Saves the body in `dir` under the name from Content-Disposition
+    // (`filename*` before `filename`), or else the last segment of the URL.
+    // Directory parts of the suggested name are dropped, so the file always
+    // lands directly in `dir`.
+    pub async fn download_suggested(
+        &self,
+        path: &str,
+        dir: impl AsRef<Path>,
+        extra_headers: Option<&dyn HeaderPairs>,
+    ) -> Result<PathBuf, ApiError> {
+        let req = self.apply_headers(self.request(Method::GET, path), extra_headers)?;
+        let _permit = self.acquire_permit().await;
+        let resp = self.send_streaming(req).await?;
+
+        let suggested = resp
+            .headers()
+            .get(CONTENT_DISPOSITION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(disposition_filename)
+            .and_then(|name| sanitize_file_name(&name));
+        let name = suggested
+            .or_else(|| url_file_name(resp.url()))
+            .unwrap_or_else(|| "download".to_string());
+
+        let dest = dir.as_ref().join(name);
+        write_body(resp, &dest, self.max_decompressed_size).await?;
+        Ok(dest)
+    }
+}
+
+// A body that fails part way is removed rather than left truncated.
+async fn write_body(resp: reqwest::Response, dest: &Path, max_len: Option<u64>) -> Result<u64, ApiError> {
+    let mut file = File::create(dest).await?;
+    let written = match copy_body(resp, &mut file, max_len).await {
+        Ok(written) => written,
+        Err(err) => {
+            drop(file);
+            let _ = tokio::fs::remove_file(dest).await;
+            return Err(err);
+        }
+    };
+    file.flush().await?;
+
+    Ok(written)
+}
+
+// Stops with DecompressionLimit before writing past `max_len` bytes.
+async fn copy_body<W>(mut resp: reqwest::Response, out: &mut W, max_len: Option<u64>) -> Result<u64, ApiError>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut written = 0u64;
+    while let Some(chunk) = resp.chunk().await? {
+        if let Some(limit) = max_len
+            && written + chunk.len() as u64 > limit
+        {
+            return Err(ApiError::DecompressionLimit { limit });
+        }
+        out.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+    }
+
+    Ok(written)
+}
+
+// `filename*` (RFC 6266 / 5987, `charset'lang'percent-encoded`) wins over
+// `filename`, which may be quoted.
+fn disposition_filename(value: &str) -> Option<String> {
+    let mut plain = None;
+    for param in split_params(value).into_iter().skip(1) {
+        let Some((name, value)) = param.split_once('=') else {
+            continue;
+        };
+        let name = name.trim();
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("filename*") {
+            let mut parts = value.splitn(3, '\'');
+            let (Some(charset), Some(_lang), Some(encoded)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let bytes: Vec<u8> = percent_decode_str(encoded).collect();
+            if charset.eq_ignore_ascii_case("utf-8") {
+                if let Ok(decoded) = String::from_utf8(bytes) {
+                    return Some(decoded);
+                }
+            } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+                return Some(bytes.into_iter().map(char::from).collect());
+            }
+        } else if name.eq_ignore_ascii_case("filename") && plain.is_none() {
+            plain = Some(unquote(value));
+        }
+    }
+    plain
+}
+
+// Splits on `;` outside of quoted strings.
+fn split_params(value: &str) -> Vec<&str> {
+    let mut params = Vec::new();
+    let (mut start, mut quoted, mut escaped) = (0, false, false);
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                params.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    params.push(&value[start..]);
+    params
+}
+
+fn unquote(value: &str) -> String {
+    let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) else {
+        return value.to_string();
+    };
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        out.push(if c == '\\' { chars.next().unwrap_or(c) } else { c });
+    }
+    out
+}
+
+// Keeps only the final path component and refuses names made of dots only
+// (`.`, `..`) or containing control characters.
+fn sanitize_file_name(name: &str) -> Option<String> {
+    let name = name.rsplit(['/', '\\']).next()?.trim();
+    let valid = !name.is_empty()
+        && name.chars().any(|c| c != '.')
+        && !name.chars().any(char::is_control);
+    valid.then(|| name.to_string())
+}
+
+fn url_file_name(url: &Url) -> Option<String> {
+    let segment = url.path_segments()?.next_back()?;
+    let decoded = percent_decode_str(segment).decode_utf8().ok()?;
+    sanitize_file_name(&decoded)
+}
@@ -0,0 +1,80 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use reqwest::{Client, Url, redirect};
+
+// HTTP version the client speaks. Without one, HTTP/2 is negotiated through
+// TLS ALPN and plain-text connections use HTTP/1.1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpVersion {
+    Http1,
+    // HTTP/2 without negotiation, also over plain text (h2c). The server
+    // must support it.
+    Http2PriorKnowledge,
+}
+
+// Options that only reqwest's native ClientBuilder can apply. The client is
+// rebuilt from the whole config whenever one of them changes.
+#[derive(Debug, Clone, Default)]
+pub(super) struct HttpConfig {
+    pub(super) resolve: Vec<(String, SocketAddr)>,
+    // None keeps reqwest's default of following up to 10 redirects.
+    pub(super) max_redirects: Option<usize>,
+    pub(super) same_host_redirects: bool,
+    pub(super) tcp_keepalive: Option<Duration>,
+    pub(super) connect_timeout: Option<Duration>,
+    pub(super) http_version: Option<HttpVersion>,
+}
+
+impl HttpConfig {
+    pub(super) fn build(&self) -> Client {
+        let mut builder = Client::builder();
+
+        for (host, addr) in &self.resolve {
+            builder = builder.resolve(host, *addr);
+        }
+
+        match self.max_redirects {
+            Some(0) => builder = builder.redirect(redirect::Policy::none()),
+            Some(max) if self.same_host_redirects => builder = builder.redirect(same_origin(max)),
+            Some(max) => builder = builder.redirect(redirect::Policy::limited(max)),
+            None if self.same_host_redirects => builder = builder.redirect(same_origin(10)),
+            None => {}
+        }
+
+        match self.http_version {
+            Some(HttpVersion::Http1) => builder = builder.http1_only(),
+            Some(HttpVersion::Http2PriorKnowledge) => builder = builder.http2_prior_knowledge(),
+            None => {}
+        }
+
+        if let Some(keepalive) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(keepalive);
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        // Same failure mode as Client::new: only a broken TLS backend or
+        // resolver setup makes this fail.
+        builder.build().expect("failed to build the HTTP client")
+    }
+}
+
+// Follows up to `max` redirects that keep the original request's origin
+// (scheme, host and port). A redirect anywhere else, including a downgrade
+// to http or another port on the same host, is not followed, so its 3xx
+// reaches the caller.
+fn same_origin(max: usize) -> redirect::Policy {
+    redirect::Policy::custom(move |attempt| {
+        let origin = attempt.previous().first().map(Url::origin);
+        if origin != Some(attempt.url().origin()) {
+            attempt.stop()
+        } else if attempt.previous().len() > max {
+            attempt.error("too many redirects")
+        } else {
+            attempt.follow()
+        }
+    })
+}
@@ -0,0 +1,69 @@
+use std::error::Error;
+use std::path::Path;
+
+use bytes::Bytes;
+use futures_util::Stream;
+use reqwest::{Body, Method};
+use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
+
+use super::{ApiClient, parse_header};
+use crate::api::{ApiError, ApiResponse, HeaderPairs};
+
+impl ApiClient {
+    // -----------------------
+    //      FILE UPLOAD
+    // -----------------------
+    // POSTs the file as the raw request body, streamed from disk. The body
+    // cannot be replayed, so a retry policy does not apply to it.
+    pub async fn upload_file(
+        &self,
+        path: &str,
+        file_path: impl AsRef<Path>,
+        content_type: &str,
+        extra_headers: Option<&dyn HeaderPairs>,
+    ) -> Result<ApiResponse, ApiError> {
+        let file = File::open(file_path).await?;
+        let len = file.metadata().await.ok().map(|m| m.len());
+
+        let (name, value) = parse_header(CONTENT_TYPE.as_str(), content_type)?;
+        let mut req = self
+            .request(Method::POST, path)
+            .header(name, value)
+            .body(Body::wrap_stream(ReaderStream::new(file)));
+        if let Some(len) = len {
+            req = req.header(CONTENT_LENGTH, len);
+        }
+        let req = self.apply_headers(req, extra_headers)?;
+
+        self.error_for_status(self.send(req).await?)
+    }
+
+    // -----------------------
+    //      STREAM UPLOAD
+    // -----------------------
+    // POSTs whatever `body` yields, for bodies whose length is not known up
+    // front: the request goes out with chunked transfer encoding and no
+    // Content-Length. Like upload_file, it is sent once, without retries.
+    pub async fn post_stream<S, E>(
+        &self,
+        path: &str,
+        body: S,
+        content_type: &str,
+        extra_headers: Option<&dyn HeaderPairs>,
+    ) -> Result<ApiResponse, ApiError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn Error + Send + Sync>> + 'static,
+    {
+        let (name, value) = parse_header(CONTENT_TYPE.as_str(), content_type)?;
+        let req = self
+            .request(Method::POST, path)
+            .header(name, value)
+            .body(Body::wrap_stream(body));
+        let req = self.apply_headers(req, extra_headers)?;
+
+        self.error_for_status(self.send(req).await?)
+    }
+}
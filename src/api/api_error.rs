@@ -0,0 +1,158 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use super::ComposedRequest;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("invalid header: {name:?}")]
+    InvalidHeader { name: String },
+
+    #[error("environment variable {name} is missing or not unicode")]
+    Env {
+        name: String,
+        #[source]
+        source: std::env::VarError,
+    },
+
+    #[error("invalid base url: {url:?}")]
+    InvalidBaseUrl { url: String },
+
+    #[error("invalid query parameters: {reason}")]
+    InvalidQuery { reason: String },
+
+    #[error("http request failed")]
+    Http(#[from] reqwest::Error),
+
+    // 401: missing or expired credentials; logging in again may help.
+    // `message` and `code` come from the error extractor, as for Status.
+    #[error("unauthorized{}", .message.as_ref().map(|m| format!(": {m}")).unwrap_or_default())]
+    Unauthorized {
+        body: String,
+        message: Option<String>,
+        code: Option<String>,
+    },
+
+    // 403: the credentials were accepted but do not allow this request.
+    #[error("forbidden{}", .message.as_ref().map(|m| format!(": {m}")).unwrap_or_default())]
+    Forbidden {
+        body: String,
+        message: Option<String>,
+        code: Option<String>,
+    },
+
+    // 412: a conditional request (If-Match, If-Unmodified-Since) lost to a
+    // concurrent change.
+    #[error("precondition failed{}", .message.as_ref().map(|m| format!(": {m}")).unwrap_or_default())]
+    PreconditionFailed {
+        body: String,
+        message: Option<String>,
+        code: Option<String>,
+    },
+
+    // Any other 4xx/5xx. `message` and `code` are filled in by the client's
+    // error extractor, when it has one.
+    #[error("unexpected status: {status}{}", .message.as_ref().map(|m| format!(": {m}")).unwrap_or_default())]
+    Status {
+        status: StatusCode,
+        body: String,
+        message: Option<String>,
+        code: Option<String>,
+    },
+
+    // A 3xx that was not followed. `location` is resolved against the
+    // request URL when it is relative.
+    #[error("unfollowed redirect: {status}")]
+    Redirect {
+        status: StatusCode,
+        location: Option<String>,
+    },
+
+    // A 429 response; `retry_after` comes from the Retry-After header.
+    #[error("rate limited")]
+    RateLimited { retry_after: Option<Duration> },
+
+    // `path` locates the offending value (`items[2].id`, `.` for the root);
+    // `body` is the start of the response body, cut at the client's decode
+    // body limit.
+    #[error("failed to decode response body")]
+    Decode {
+        #[source]
+        source: serde_json::Error,
+        path: String,
+        body: String,
+    },
+
+    #[error("failed to decode form-encoded response body")]
+    DecodeForm(#[source] serde_urlencoded::de::Error),
+
+    #[error("expected {expected} bytes but received {received}")]
+    ContentLengthMismatch { expected: u64, received: u64 },
+
+    // A ranged download chunk came back without a 206 whose Content-Range
+    // covers exactly the requested bytes.
+    #[error("expected content range {expected:?} but received {received:?}")]
+    RangeMismatch {
+        expected: String,
+        received: Option<String>,
+    },
+
+    // The decoded body grew past the client's limit, whatever its wire size
+    // or Content-Length.
+    #[error("decompressed body exceeds {limit} bytes")]
+    DecompressionLimit { limit: u64 },
+
+    #[cfg(feature = "jsonschema")]
+    #[error("response does not match the schema: {}", .0.join("; "))]
+    SchemaViolation(Vec<String>),
+
+    #[cfg(feature = "jsonschema")]
+    #[error("invalid json schema: {reason}")]
+    InvalidSchema { reason: String },
+
+    #[error("json-rpc error {code}: {message}")]
+    JsonRpc { code: i64, message: String },
+
+    // Dry-run mode stops every request here instead of sending it.
+    #[error("dry run: {} {} was not sent", .0.method, .0.url)]
+    DryRun(Box<ComposedRequest>),
+
+    #[error("no empty page after {max_pages} pages")]
+    PageLimitExceeded { max_pages: u32 },
+
+    // bulk_create: every item of a failed batch carries the same batch error.
+    #[error("bulk batch {batch} failed")]
+    BulkBatch {
+        batch: usize,
+        #[source]
+        source: Arc<ApiError>,
+    },
+
+    #[error("bulk batch returned {received} results for {expected} items")]
+    BulkResultCount { expected: usize, received: usize },
+
+    // get_first_ok: every candidate failed. Holds each path with its error.
+    #[error("all {} requests failed", .0.len())]
+    AllFailed(Vec<(String, ApiError)>),
+
+    // A replayed request with no matching recording, or a malformed one.
+    #[error("cassette: {reason}")]
+    Cassette { reason: String },
+
+    #[error("i/o error")]
+    Io(#[from] std::io::Error),
+}
+
+impl ApiError {
+    pub(crate) fn decode(source: serde_json::Error, path: String, body: &[u8], limit: usize) -> Self {
+        let body = String::from_utf8_lossy(&body[..body.len().min(limit)]).into_owned();
+        ApiError::Decode { source, path, body }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+#[path = "api_error_tests.rs"]
+mod api_error_tests;
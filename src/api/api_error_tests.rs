@@ -0,0 +1,52 @@
+use super::*;
+use std::error::Error as _;
+
+fn decode_error() -> ApiError {
+    let err = serde_json::from_str::<u32>("\"not a number\"").unwrap_err();
+    ApiError::decode(err, ".".to_string(), b"\"not a number\"", 1024)
+}
+
+#[test]
+fn give_decode_error_when_source_then_serde_error_should_be_returned() {
+    // give
+    let err = decode_error();
+
+    // when
+    let source = err.source().expect("source expected");
+
+    // then
+    assert!(source.downcast_ref::<serde_json::Error>().is_some());
+}
+
+#[test]
+fn give_decode_error_when_converted_to_anyhow_then_chain_should_include_cause() {
+    fn load() -> anyhow::Result<u32> {
+        Err(decode_error())?
+    }
+
+    // give / when
+    let err = load().unwrap_err();
+
+    // then
+    let chain: Vec<String> = err.chain().map(|e| e.to_string()).collect();
+    assert_eq!(chain.len(), 2);
+    assert_eq!(chain[0], "failed to decode response body");
+    assert!(chain[1].contains("invalid type"), "unexpected cause: {}", chain[1]);
+    assert!(err.root_cause().downcast_ref::<serde_json::Error>().is_some());
+}
+
+#[tokio::test]
+async fn give_connection_failure_when_converted_to_anyhow_then_reqwest_error_should_be_in_chain() {
+    // give
+    let client = crate::api::ApiClient::new("http://127.0.0.1:1");
+
+    // when
+    let err: anyhow::Error = client
+        .get_json::<serde_json::Value>("/unreachable", None)
+        .await
+        .unwrap_err()
+        .into();
+
+    // then
+    assert!(err.chain().any(|e| e.downcast_ref::<reqwest::Error>().is_some()));
+}
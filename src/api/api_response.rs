@@ -0,0 +1,285 @@
+use bytes::{Bytes, BytesMut};
+use futures_util::StreamExt;
+use encoding_rs::{Encoding, UTF_8};
+use reqwest::header::{CONTENT_TYPE, HeaderMap, LINK, LOCATION, RETRY_AFTER};
+use reqwest::{StatusCode, Url};
+use serde::de::DeserializeOwned;
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::link_header::parse_into;
+use super::{ApiError, LinkRelations};
+use super::runtime::unix_now;
+
+const FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+// Bytes of the body kept on ApiError::Decode unless the client sets its own
+// limit.
+pub(crate) const DECODE_BODY_LIMIT: usize = 1024;
+
+#[derive(Debug, Clone)]
+pub struct ApiResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+    pub final_url: String,
+}
+
+impl ApiResponse {
+    // With `expected_len` set, a body that ends early or overruns is reported
+    // as ContentLengthMismatch instead of being returned or failing opaquely.
+    // `max_len` caps the decoded (decompressed) size; reading stops as soon
+    // as it is exceeded.
+    pub(crate) async fn read(
+        resp: reqwest::Response,
+        expected_len: Option<u64>,
+        max_len: Option<u64>,
+    ) -> Result<Self, ApiError> {
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let final_url = resp.url().to_string();
+
+        let mut body = BytesMut::new();
+        let mut stream = resp.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(chunk) => {
+                    if let Some(limit) = max_len
+                        && (body.len() + chunk.len()) as u64 > limit
+                    {
+                        return Err(ApiError::DecompressionLimit { limit });
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                Err(err) => {
+                    return Err(match expected_len {
+                        Some(expected) => ApiError::ContentLengthMismatch {
+                            expected,
+                            received: body.len() as u64,
+                        },
+                        None => err.into(),
+                    });
+                }
+            }
+        }
+
+        if let Some(expected) = expected_len {
+            let received = body.len() as u64;
+            if received != expected {
+                return Err(ApiError::ContentLengthMismatch { expected, received });
+            }
+        }
+
+        Ok(Self {
+            status,
+            headers,
+            body: body.freeze(),
+            final_url,
+        })
+    }
+
+    pub fn json<T>(&self) -> Result<T, ApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.json_with_body_limit(DECODE_BODY_LIMIT)
+    }
+
+    // Like `json`, keeping at most `limit` bytes of the body on a Decode
+    // error.
+    pub fn json_with_body_limit<T>(&self, limit: usize) -> Result<T, ApiError>
+    where
+        T: DeserializeOwned,
+    {
+        from_json(&self.body, limit)
+    }
+
+    // Picks the format from Content-Type: form-urlencoded bodies go through
+    // serde_urlencoded, everything else (including no Content-Type) is JSON.
+    pub fn decode<T>(&self) -> Result<T, ApiError>
+    where
+        T: DeserializeOwned,
+    {
+        self.decode_with_body_limit(DECODE_BODY_LIMIT)
+    }
+
+    pub fn decode_with_body_limit<T>(&self, limit: usize) -> Result<T, ApiError>
+    where
+        T: DeserializeOwned,
+    {
+        if self.media_type().is_some_and(|m| m.eq_ignore_ascii_case(FORM_URLENCODED)) {
+            return serde_urlencoded::from_bytes(&self.body).map_err(ApiError::DecodeForm);
+        }
+
+        self.json_with_body_limit(limit)
+    }
+
+    pub fn text(&self) -> String {
+        let encoding = self
+            .charset()
+            .and_then(|label| Encoding::for_label(label.as_bytes()))
+            .unwrap_or(UTF_8);
+        let (text, _, _) = encoding.decode(&self.body);
+        text.into_owned()
+    }
+
+    // Relations from every Link header; relative URLs are resolved against
+    // the response URL.
+    pub fn links(&self) -> LinkRelations {
+        let mut links = LinkRelations::default();
+        for value in self.header_all(LINK.as_str()) {
+            parse_into(&mut links, value);
+        }
+        links.map_urls(|url| self.resolve(url))
+    }
+
+    fn location(&self) -> Option<String> {
+        let location = self.header(LOCATION.as_str())?;
+        Some(self.resolve(location))
+    }
+
+    fn resolve(&self, url: &str) -> String {
+        let resolved = Url::parse(&self.final_url).and_then(|base| base.join(url));
+        resolved.map_or_else(|_| url.to_string(), String::from)
+    }
+
+    fn media_type(&self) -> Option<&str> {
+        let content_type = self.headers.get(CONTENT_TYPE)?.to_str().ok()?;
+        content_type.split(';').next().map(str::trim)
+    }
+
+    fn charset(&self) -> Option<&str> {
+        let content_type = self.headers.get(CONTENT_TYPE)?.to_str().ok()?;
+        content_type.split(';').skip(1).find_map(|param| {
+            let (name, value) = param.split_once('=')?;
+            name.trim()
+                .eq_ignore_ascii_case("charset")
+                .then(|| value.trim().trim_matches('"'))
+        })
+    }
+
+    // Case-insensitive lookup; values that are not visible ASCII are skipped.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name)?.to_str().ok()
+    }
+
+    // The server's answer to a `Prefer` request header, e.g. "return=minimal".
+    pub fn preference_applied(&self) -> Option<&str> {
+        self.header("Preference-Applied")
+    }
+
+    pub fn header_all(&self, name: &str) -> Vec<&str> {
+        self.headers
+            .get_all(name)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect()
+    }
+
+    // 4xx/5xx become Status errors (Unauthorized, Forbidden, PreconditionFailed
+    // and RateLimited for 401, 403, 412 and 429). Unfollowed redirects become
+    // Redirect errors, since their body is not the resource; 304 is left
+    // alone for conditional requests.
+    pub fn error_for_status(self) -> Result<Self, ApiError> {
+        self.error_for_status_at(unix_now())
+    }
+
+    // error_for_status with `now` (time since the Unix epoch) as the
+    // reference for an HTTP-date Retry-After.
+    pub(crate) fn error_for_status_at(self, now: Duration) -> Result<Self, ApiError> {
+        if is_error_status(self.status) {
+            return Err(self.into_status_error(now));
+        }
+
+        Ok(self)
+    }
+
+    // For callers that stream the body: passes successful responses through
+    // untouched and buffers the body only to build the error.
+    pub(crate) async fn check_streaming(
+        resp: reqwest::Response,
+        now: Duration,
+        max_len: Option<u64>,
+    ) -> Result<reqwest::Response, ApiError> {
+        if is_error_status(resp.status()) {
+            return Err(Self::read(resp, None, max_len).await?.into_status_error(now));
+        }
+
+        Ok(resp)
+    }
+
+    pub(crate) fn into_status_error(self, now: Duration) -> ApiError {
+        if self.status.is_redirection() {
+            return ApiError::Redirect {
+                status: self.status,
+                location: self.location(),
+            };
+        }
+
+        if self.status == StatusCode::TOO_MANY_REQUESTS {
+            return ApiError::RateLimited {
+                retry_after: retry_after(&self.headers, now),
+            };
+        }
+
+        let body = String::from_utf8_lossy(&self.body).into_owned();
+        let (message, code) = (None, None);
+        match self.status {
+            StatusCode::UNAUTHORIZED => ApiError::Unauthorized { body, message, code },
+            StatusCode::FORBIDDEN => ApiError::Forbidden { body, message, code },
+            StatusCode::PRECONDITION_FAILED => ApiError::PreconditionFailed { body, message, code },
+            status => ApiError::Status {
+                status,
+                body,
+                message,
+                code,
+            },
+        }
+    }
+}
+
+// Retry-After is either a number of seconds or an HTTP date, measured from
+// `now`. A date in the past means "now".
+fn retry_after(headers: &HeaderMap, now: Duration) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let at = httpdate::parse_http_date(value).ok()?;
+    let at = at.duration_since(UNIX_EPOCH).ok()?;
+    Some(at.saturating_sub(now))
+}
+
+// serde_json::from_slice that also records where in the document decoding
+// failed.
+pub(crate) fn from_json<T>(bytes: &[u8], limit: usize) -> Result<T, ApiError>
+where
+    T: DeserializeOwned,
+{
+    let mut de = serde_json::Deserializer::from_slice(bytes);
+    let mut track = serde_path_to_error::Track::new();
+    let value = T::deserialize(serde_path_to_error::Deserializer::new(&mut de, &mut track))
+        .and_then(|value| de.end().map(|()| value));
+
+    value.map_err(|source| ApiError::decode(source, track.path().to_string(), bytes, limit))
+}
+
+// For values that were already parsed out of `body`.
+pub(crate) fn from_json_value<T>(value: serde_json::Value, body: &[u8], limit: usize) -> Result<T, ApiError>
+where
+    T: DeserializeOwned,
+{
+    serde_path_to_error::deserialize(value).map_err(|err| {
+        let path = err.path().to_string();
+        ApiError::decode(err.into_inner(), path, body, limit)
+    })
+}
+
+fn is_error_status(status: StatusCode) -> bool {
+    status.is_client_error()
+        || status.is_server_error()
+        || (status.is_redirection() && status != StatusCode::NOT_MODIFIED)
+}
+
+#[cfg(test)]
+#[path = "api_response_tests.rs"]
+mod api_response_tests;
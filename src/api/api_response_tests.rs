@@ -0,0 +1,225 @@
+use super::*;
+use reqwest::header::HeaderValue;
+
+fn response(content_type: Option<&str>, body: &'static [u8]) -> ApiResponse {
+    let mut headers = HeaderMap::new();
+    if let Some(content_type) = content_type {
+        headers.insert(CONTENT_TYPE, HeaderValue::from_str(content_type).unwrap());
+    }
+
+    ApiResponse {
+        status: StatusCode::OK,
+        headers,
+        body: Bytes::from_static(body),
+        final_url: "http://localhost/".to_string(),
+    }
+}
+
+#[test]
+fn give_no_content_type_when_text_then_body_should_be_decoded_as_utf8() {
+    // give
+    let resp = response(None, "café".as_bytes());
+
+    // when
+    let text = resp.text();
+
+    // then
+    assert_eq!(text, "café");
+}
+
+#[test]
+fn give_quoted_charset_when_text_then_declared_encoding_should_be_used() {
+    // give
+    let resp = response(Some("text/plain; Charset=\"windows-1252\""), b"caf\xe9");
+
+    // when
+    let text = resp.text();
+
+    // then
+    assert_eq!(text, "café");
+}
+
+#[test]
+fn give_unknown_charset_when_text_then_utf8_should_be_the_fallback() {
+    // give
+    let resp = response(Some("text/plain; charset=made-up"), "naïve".as_bytes());
+
+    // when
+    let text = resp.text();
+
+    // then
+    assert_eq!(text, "naïve");
+}
+
+fn rate_limited(retry_after: Option<&str>) -> ApiResponse {
+    let mut resp = response(None, b"slow down");
+    resp.status = StatusCode::TOO_MANY_REQUESTS;
+    if let Some(value) = retry_after {
+        resp.headers
+            .insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+    }
+    resp
+}
+
+#[test]
+fn give_429_with_retry_after_seconds_when_error_for_status_then_should_be_rate_limited() {
+    // give
+    let resp = rate_limited(Some("5"));
+
+    // when
+    let err = resp.error_for_status().unwrap_err();
+
+    // then
+    assert!(matches!(
+        err,
+        ApiError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(5)
+    ));
+}
+
+#[test]
+fn give_429_with_http_date_when_error_for_status_at_then_retry_after_should_count_down_from_now() {
+    // give
+    // Wed, 21 Oct 2015 07:28:00 GMT
+    let now = Duration::from_secs(1_445_412_480);
+    let resp = rate_limited(Some("Wed, 21 Oct 2015 07:30:00 GMT"));
+
+    // when
+    let err = resp.error_for_status_at(now).unwrap_err();
+
+    // then
+    assert!(
+        matches!(err, ApiError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(120)),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[test]
+fn give_429_with_past_or_missing_retry_after_when_error_for_status_then_should_be_zero_or_none() {
+    // give
+    let past = rate_limited(Some("Wed, 21 Oct 2015 07:28:00 GMT"));
+    let missing = rate_limited(None);
+
+    // when
+    let past = past.error_for_status().unwrap_err();
+    let missing = missing.error_for_status().unwrap_err();
+
+    // then
+    assert!(matches!(past, ApiError::RateLimited { retry_after: Some(Duration::ZERO) }));
+    assert!(matches!(missing, ApiError::RateLimited { retry_after: None }));
+}
+
+#[test]
+fn give_mixed_case_names_when_header_then_lookup_should_ignore_case() {
+    // give
+    let mut resp = response(Some("application/json"), b"{}");
+    resp.headers
+        .insert("X-Request-Id", HeaderValue::from_static("req-42"));
+
+    // when / then
+    assert_eq!(resp.header("x-request-id"), Some("req-42"));
+    assert_eq!(resp.header("X-REQUEST-ID"), Some("req-42"));
+    assert_eq!(resp.header("Content-Type"), Some("application/json"));
+    assert_eq!(resp.header("X-Missing"), None);
+    assert_eq!(resp.header("not a header name"), None);
+}
+
+#[test]
+fn give_repeated_header_when_header_all_then_every_value_should_be_returned_in_order() {
+    // give
+    let mut resp = response(None, b"");
+    resp.headers
+        .append("Set-Cookie", HeaderValue::from_static("a=1"));
+    resp.headers
+        .append("set-cookie", HeaderValue::from_static("b=2"));
+
+    // when
+    let cookies = resp.header_all("SET-COOKIE");
+
+    // then
+    assert_eq!(cookies, ["a=1", "b=2"]);
+    assert!(resp.header_all("x-missing").is_empty());
+}
+
+#[test]
+fn give_no_preference_applied_header_when_preference_applied_then_should_return_none() {
+    // give
+    let resp = response(None, b"");
+
+    // when / then
+    assert_eq!(resp.preference_applied(), None);
+}
+
+#[derive(Debug, serde::Deserialize, PartialEq)]
+struct Outcome {
+    error: String,
+    code: u32,
+}
+
+#[test]
+fn give_form_content_type_when_decode_then_body_should_be_parsed_as_form() {
+    // give
+    let resp = response(
+        Some("application/x-www-form-urlencoded; charset=utf-8"),
+        b"error=invalid+grant&code=40",
+    );
+
+    // when
+    let outcome: Outcome = resp.decode().unwrap();
+
+    // then
+    assert_eq!(outcome, Outcome { error: "invalid grant".into(), code: 40 });
+}
+
+#[test]
+fn give_json_or_missing_content_type_when_decode_then_body_should_be_parsed_as_json() {
+    // give
+    let json = response(Some("application/problem+json"), br#"{"error":"x","code":1}"#);
+    let untyped = response(None, br#"{"error":"y","code":2}"#);
+
+    // when / then
+    assert_eq!(json.decode::<Outcome>().unwrap(), Outcome { error: "x".into(), code: 1 });
+    assert_eq!(untyped.decode::<Outcome>().unwrap(), Outcome { error: "y".into(), code: 2 });
+}
+
+#[test]
+fn give_malformed_form_body_when_decode_then_decode_form_error_should_be_returned() {
+    // give
+    let resp = response(Some("application/x-www-form-urlencoded"), b"error=x&code=not-a-number");
+
+    // when
+    let result = resp.decode::<Outcome>();
+
+    // then
+    assert!(matches!(result, Err(ApiError::DecodeForm(_))));
+}
+
+#[test]
+fn give_not_modified_when_error_for_status_then_response_should_pass_through() {
+    // give
+    let mut resp = response(None, b"");
+    resp.status = StatusCode::NOT_MODIFIED;
+
+    // when / then
+    assert!(resp.error_for_status().is_ok());
+}
+
+#[test]
+fn give_several_link_headers_when_links_then_relations_should_be_merged_and_resolved() {
+    // give
+    let mut resp = response(None, b"[]");
+    resp.final_url = "https://api.example.com/v1/items?page=2".to_string();
+    resp.headers.append(LINK, HeaderValue::from_static(r#"</v1/items?page=3>; rel="next""#));
+    resp.headers.append(
+        LINK,
+        HeaderValue::from_static(r#"<?page=1>; rel="first", <https://cdn.example.com/items?page=9>; rel=last"#),
+    );
+
+    // when
+    let links = resp.links();
+
+    // then
+    assert_eq!(links.next.as_deref(), Some("https://api.example.com/v1/items?page=3"));
+    assert_eq!(links.first.as_deref(), Some("https://api.example.com/v1/items?page=1"));
+    assert_eq!(links.last.as_deref(), Some("https://cdn.example.com/items?page=9"));
+    assert_eq!(links.prev, None);
+}
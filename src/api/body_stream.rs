@@ -0,0 +1,64 @@
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use futures_util::{FutureExt, Stream};
+use http_body_util::BodyExt;
+use reqwest::header::HeaderMap;
+use tokio::sync::SemaphorePermit;
+
+use super::ApiError;
+
+// A response body read chunk by chunk. Trailers sent after the body (chunked
+// HTTP/1.1 trailers or an HTTP/2 trailing HEADERS frame) are kept and can be
+// read with `trailers` once the stream has ended. Holds the client's
+// concurrency slot until dropped.
+pub struct BodyStream<'a> {
+    body: reqwest::Body,
+    trailers: Option<HeaderMap>,
+    _permit: Option<SemaphorePermit<'a>>,
+}
+
+impl<'a> BodyStream<'a> {
+    pub(crate) fn new(resp: reqwest::Response, permit: Option<SemaphorePermit<'a>>) -> Self {
+        Self {
+            body: resp.into(),
+            trailers: None,
+            _permit: permit,
+        }
+    }
+
+    // None until the stream has returned its last item, or when the server
+    // sent no trailers.
+    pub fn trailers(&self) -> Option<&HeaderMap> {
+        self.trailers.as_ref()
+    }
+
+    pub fn trailer(&self, name: &str) -> Option<&str> {
+        self.trailers.as_ref()?.get(name)?.to_str().ok()
+    }
+}
+
+impl Stream for BodyStream<'_> {
+    type Item = Result<Bytes, ApiError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let frame = match ready!(this.body.frame().poll_unpin(cx)) {
+                Some(Ok(frame)) => frame,
+                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                None => return Poll::Ready(None),
+            };
+
+            match frame.into_data() {
+                Ok(data) => return Poll::Ready(Some(Ok(data))),
+                Err(frame) => {
+                    if let Ok(trailers) = frame.into_trailers() {
+                        this.trailers.get_or_insert_default().extend(trailers);
+                    }
+                }
+            }
+        }
+    }
+}
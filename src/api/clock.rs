@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::runtime;
+
+// Time source for the retry loop. Tests swap in a fake clock so backoff and
+// time budgets can be exercised without actually waiting.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Clock: Send + Sync {
+    // Monotonic time since an arbitrary, fixed origin.
+    fn now(&self) -> Duration;
+
+    // Wall-clock time since the Unix epoch, for turning an HTTP-date
+    // Retry-After into a delay.
+    fn unix_now(&self) -> Duration {
+        runtime::unix_now()
+    }
+
+    async fn sleep(&self, duration: Duration);
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        runtime::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        runtime::sleep(duration).await;
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+#[path = "./clock_tests.rs"]
+mod clock_tests;
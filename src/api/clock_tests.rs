@@ -0,0 +1,14 @@
+use super::*;
+
+#[tokio::test]
+async fn give_system_clock_when_sleeping_then_now_should_advance_by_at_least_the_sleep() {
+    // give
+    let clock = SystemClock;
+    let before = clock.now();
+
+    // when
+    clock.sleep(Duration::from_millis(20)).await;
+
+    // then
+    assert!(clock.now() - before >= Duration::from_millis(20));
+}
@@ -0,0 +1,28 @@
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Request};
+
+// Everything a call would put on the wire, as returned by preview_request
+// and by dry-run mode. Streaming bodies have no bytes to show and appear as
+// None.
+#[derive(Debug, Clone)]
+pub struct ComposedRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: Option<Bytes>,
+}
+
+impl ComposedRequest {
+    pub(crate) fn from_request(request: &Request) -> Self {
+        Self {
+            method: request.method().clone(),
+            url: request.url().to_string(),
+            headers: request.headers().clone(),
+            body: request
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map(Bytes::copy_from_slice),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// Case convention used by the client's own serialization helpers (e.g. the
+// auth form). Caller-provided bodies are serialized as-is by serde.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FieldNaming {
+    #[default]
+    SnakeCase,
+    CamelCase,
+}
+
+impl FieldNaming {
+    pub fn apply(&self, field: &str) -> String {
+        match self {
+            FieldNaming::SnakeCase => field.to_string(),
+            FieldNaming::CamelCase => {
+                let mut out = String::with_capacity(field.len());
+                let mut upper_next = false;
+                for c in field.chars() {
+                    if c == '_' {
+                        upper_next = !out.is_empty();
+                    } else if upper_next {
+                        out.extend(c.to_uppercase());
+                        upper_next = false;
+                    } else {
+                        out.push(c);
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "field_naming_tests.rs"]
+mod field_naming_tests;
@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn give_snake_case_when_apply_then_field_should_be_unchanged() {
+    // give
+    let naming = FieldNaming::SnakeCase;
+
+    // when
+    let field = naming.apply("client_secret");
+
+    // then
+    assert_eq!(field, "client_secret");
+}
+
+#[test]
+fn give_camel_case_when_apply_then_underscores_should_become_capitals() {
+    // give
+    let naming = FieldNaming::CamelCase;
+
+    // when / then
+    assert_eq!(naming.apply("client_id"), "clientId");
+    assert_eq!(naming.apply("refresh_token_value"), "refreshTokenValue");
+    assert_eq!(naming.apply("_leading"), "leading");
+    assert_eq!(naming.apply("scope"), "scope");
+}
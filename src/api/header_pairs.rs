@@ -0,0 +1,71 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+// Anything that can hand out (name, value) header pairs: slices, arrays,
+// vecs and maps of string-like keys and values.
+pub trait HeaderPairs: Sync {
+    fn header_pairs(&self) -> Vec<(&str, &str)>;
+}
+
+impl<K, V> HeaderPairs for [(K, V)]
+where
+    K: AsRef<str> + Sync,
+    V: AsRef<str> + Sync,
+{
+    fn header_pairs(&self) -> Vec<(&str, &str)> {
+        self.iter().map(|(k, v)| (k.as_ref(), v.as_ref())).collect()
+    }
+}
+
+impl<K, V, const N: usize> HeaderPairs for [(K, V); N]
+where
+    K: AsRef<str> + Sync,
+    V: AsRef<str> + Sync,
+{
+    fn header_pairs(&self) -> Vec<(&str, &str)> {
+        self.as_slice().header_pairs()
+    }
+}
+
+impl<K, V> HeaderPairs for Vec<(K, V)>
+where
+    K: AsRef<str> + Sync,
+    V: AsRef<str> + Sync,
+{
+    fn header_pairs(&self) -> Vec<(&str, &str)> {
+        self.as_slice().header_pairs()
+    }
+}
+
+impl<K, V, S> HeaderPairs for HashMap<K, V, S>
+where
+    K: AsRef<str> + Sync,
+    V: AsRef<str> + Sync,
+    S: BuildHasher + Sync,
+{
+    fn header_pairs(&self) -> Vec<(&str, &str)> {
+        self.iter().map(|(k, v)| (k.as_ref(), v.as_ref())).collect()
+    }
+}
+
+impl<K, V> HeaderPairs for BTreeMap<K, V>
+where
+    K: AsRef<str> + Sync,
+    V: AsRef<str> + Sync,
+{
+    fn header_pairs(&self) -> Vec<(&str, &str)> {
+        self.iter().map(|(k, v)| (k.as_ref(), v.as_ref())).collect()
+    }
+}
+
+// Lets a slice reference stand in as `Some(&headers)` where `headers` is a
+// `&[(&str, &str)]`: the slice itself is unsized and cannot become a
+// `&dyn HeaderPairs`, but a reference to it can.
+impl<T> HeaderPairs for &T
+where
+    T: HeaderPairs + ?Sized,
+{
+    fn header_pairs(&self) -> Vec<(&str, &str)> {
+        (**self).header_pairs()
+    }
+}
@@ -0,0 +1,142 @@
+use serde::de::Error as _;
+
+// Splits a top-level JSON array into the raw bytes of its elements as the
+// body arrives, so each element can be decoded without buffering the whole
+// array. Only framing is checked here; the elements themselves are validated
+// when they are deserialized.
+#[derive(Debug, Default)]
+pub(crate) struct ArraySplitter {
+    buf: Vec<u8>,
+    pos: usize,
+    state: State,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum State {
+    #[default]
+    Open,
+    BeforeElement {
+        after_comma: bool,
+    },
+    Element {
+        start: usize,
+        depth: u32,
+        in_string: bool,
+        escaped: bool,
+    },
+    Closed,
+}
+
+impl ArraySplitter {
+    pub(crate) fn push(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+    }
+
+    // Next complete element, or None when more input is needed (or the array
+    // has been closed).
+    pub(crate) fn next_element(&mut self) -> Result<Option<Vec<u8>>, serde_json::Error> {
+        while self.pos < self.buf.len() {
+            let byte = self.buf[self.pos];
+            self.pos += 1;
+
+            match &mut self.state {
+                State::Open => match byte {
+                    b'[' => self.state = State::BeforeElement { after_comma: false },
+                    b if b.is_ascii_whitespace() => {}
+                    _ => return Err(framing("expected `[` at the start of the body")),
+                },
+                State::BeforeElement { after_comma } => match byte {
+                    b if b.is_ascii_whitespace() => {}
+                    b']' if !*after_comma => self.state = State::Closed,
+                    b']' | b',' => return Err(framing("expected an array element")),
+                    _ => {
+                        self.state = State::Element {
+                            start: self.pos - 1,
+                            depth: 0,
+                            in_string: false,
+                            escaped: false,
+                        };
+                        // Re-scan the first byte so quotes and brackets count.
+                        self.pos -= 1;
+                    }
+                },
+                State::Element {
+                    start,
+                    depth,
+                    in_string,
+                    escaped,
+                } => {
+                    if *in_string {
+                        match byte {
+                            _ if *escaped => *escaped = false,
+                            b'\\' => *escaped = true,
+                            b'"' => *in_string = false,
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    match byte {
+                        b'"' => *in_string = true,
+                        b'[' | b'{' => *depth += 1,
+                        b']' | b'}' if *depth > 0 => *depth -= 1,
+                        b',' | b']' if *depth == 0 => {
+                            let element = self.buf[*start..self.pos - 1].trim_ascii().to_vec();
+                            self.state = if byte == b',' {
+                                State::BeforeElement { after_comma: true }
+                            } else {
+                                State::Closed
+                            };
+                            self.compact();
+                            return Ok(Some(element));
+                        }
+                        b'}' => return Err(framing("unbalanced `}` in array element")),
+                        _ => {}
+                    }
+                }
+                State::Closed => {
+                    if !byte.is_ascii_whitespace() {
+                        return Err(framing("unexpected data after the closing `]`"));
+                    }
+                }
+            }
+        }
+
+        self.compact();
+        Ok(None)
+    }
+
+    // Called once the body has ended.
+    pub(crate) fn finish(&self) -> Result<(), serde_json::Error> {
+        match self.state {
+            State::Closed => Ok(()),
+            _ => Err(framing("body ended before the closing `]`")),
+        }
+    }
+
+    // Drops bytes that are no longer needed so memory stays bounded by the
+    // largest element rather than the whole array.
+    fn compact(&mut self) {
+        let keep_from = match self.state {
+            State::Element { start, .. } => start,
+            _ => self.pos,
+        };
+        if keep_from == 0 {
+            return;
+        }
+
+        self.buf.drain(..keep_from);
+        self.pos -= keep_from;
+        if let State::Element { start, .. } = &mut self.state {
+            *start -= keep_from;
+        }
+    }
+}
+
+fn framing(msg: &str) -> serde_json::Error {
+    serde_json::Error::custom(msg)
+}
+
+#[cfg(test)]
+#[path = "json_array_tests.rs"]
+mod json_array_tests;
@@ -0,0 +1,56 @@
+use super::*;
+
+fn split(chunks: &[&str]) -> Result<Vec<String>, serde_json::Error> {
+    let mut splitter = ArraySplitter::default();
+    let mut out = Vec::new();
+    for chunk in chunks {
+        splitter.push(chunk.as_bytes());
+        while let Some(element) = splitter.next_element()? {
+            out.push(String::from_utf8(element).unwrap());
+        }
+    }
+    splitter.finish()?;
+    Ok(out)
+}
+
+#[test]
+fn give_array_in_one_chunk_when_splitting_then_each_element_should_be_returned() {
+    // give
+    let body = r#" [ {"a": 1}, 2 , "three", [4, [5]], null ] "#;
+
+    // when
+    let elements = split(&[body]).unwrap();
+
+    // then
+    assert_eq!(elements, [r#"{"a": 1}"#, "2", r#""three""#, "[4, [5]]", "null"]);
+}
+
+#[test]
+fn give_elements_split_across_chunks_when_splitting_then_strings_and_nesting_should_be_respected() {
+    // give
+    let chunks = [r#"[{"s": "a, b ] \"q"#, r#"uoted\" }"}, {"n": [1,"#, "2]}]"];
+
+    // when
+    let elements = split(&chunks).unwrap();
+
+    // then
+    assert_eq!(elements, [r#"{"s": "a, b ] \"quoted\" }"}"#, r#"{"n": [1,2]}"#]);
+}
+
+#[test]
+fn give_empty_array_when_splitting_then_no_elements_should_be_returned() {
+    // give / when
+    let elements = split(&["[", " ]"]).unwrap();
+
+    // then
+    assert!(elements.is_empty());
+}
+
+#[test]
+fn give_malformed_framing_when_splitting_then_error_should_be_returned() {
+    // give / when / then
+    assert!(split(&[r#"{"a": 1}"#]).is_err());
+    assert!(split(&["[1, 2"]).is_err());
+    assert!(split(&["[1, ]"]).is_err());
+    assert!(split(&["[1] 2"]).is_err());
+}
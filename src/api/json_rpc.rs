@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub(crate) struct RpcRequest<'a, P: ?Sized> {
+    jsonrpc: &'static str,
+    method: &'a str,
+    params: &'a P,
+    id: u64,
+}
+
+impl<'a, P: ?Sized> RpcRequest<'a, P> {
+    pub(crate) fn new(method: &'a str, params: &'a P, id: u64) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            method,
+            params,
+            id,
+        }
+    }
+}
+
+// `result` stays untyped until `error` has been ruled out, so a null result
+// still decodes into `()` or `Option<T>`.
+#[derive(Deserialize)]
+pub(crate) struct RpcResponse {
+    #[serde(default)]
+    pub(crate) result: serde_json::Value,
+    pub(crate) error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RpcError {
+    pub(crate) code: i64,
+    pub(crate) message: String,
+}
@@ -0,0 +1,153 @@
+// Targets of a `Link` header (RFC 8288, formerly 5988) by relation type.
+// A link with several relations (`rel="next last"`) is listed under each.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkRelations {
+    pub first: Option<String>,
+    pub prev: Option<String>,
+    pub next: Option<String>,
+    pub last: Option<String>,
+    // Any other relation, in header order.
+    pub other: Vec<(String, String)>,
+}
+
+impl LinkRelations {
+    // Case-insensitive, like relation types themselves. `previous` is
+    // accepted for `prev`.
+    pub fn get(&self, rel: &str) -> Option<&str> {
+        let rel = rel.to_ascii_lowercase();
+        let known = match rel.as_str() {
+            "first" => &self.first,
+            "prev" | "previous" => &self.prev,
+            "next" => &self.next,
+            "last" => &self.last,
+            _ => {
+                return self
+                    .other
+                    .iter()
+                    .find(|(name, _)| *name == rel)
+                    .map(|(_, url)| url.as_str());
+            }
+        };
+        known.as_deref()
+    }
+
+    // The first link for a relation wins, as with repeated Link headers.
+    fn insert(&mut self, rel: &str, url: &str) {
+        let rel = rel.to_ascii_lowercase();
+        let slot = match rel.as_str() {
+            "first" => &mut self.first,
+            "prev" | "previous" => &mut self.prev,
+            "next" => &mut self.next,
+            "last" => &mut self.last,
+            _ => {
+                if self.get(&rel).is_none() {
+                    self.other.push((rel, url.to_string()));
+                }
+                return;
+            }
+        };
+        slot.get_or_insert_with(|| url.to_string());
+    }
+
+    pub(crate) fn map_urls(mut self, f: impl Fn(&str) -> String) -> Self {
+        let known = [&mut self.first, &mut self.prev, &mut self.next, &mut self.last];
+        for url in known.into_iter().flatten() {
+            *url = f(url);
+        }
+        for (_, url) in &mut self.other {
+            *url = f(url);
+        }
+        self
+    }
+}
+
+// Parses one Link header value: `<url>; rel="next"; title="a, b", <url>; ...`.
+// URLs are returned as written. Links without a `rel` and malformed parts are
+// skipped rather than failing the whole header.
+pub fn parse_link_header(value: &str) -> LinkRelations {
+    let mut relations = LinkRelations::default();
+    parse_into(&mut relations, value);
+    relations
+}
+
+pub(crate) fn parse_into(relations: &mut LinkRelations, value: &str) {
+    let mut rest = value;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        let Some(after) = rest.strip_prefix('<') else {
+            // Not a link; resynchronize on the next one.
+            match rest.find(',') {
+                Some(comma) => {
+                    rest = &rest[comma..];
+                    continue;
+                }
+                None => return,
+            }
+        };
+        let Some(end) = after.find('>') else {
+            return;
+        };
+        let url = after[..end].trim();
+        rest = &after[end + 1..];
+
+        let mut rel = None;
+        loop {
+            rest = rest.trim_start();
+            let Some(after) = rest.strip_prefix(';') else {
+                break;
+            };
+            let (name, value, remaining) = parse_param(after);
+            rest = remaining;
+            // Only the first rel parameter counts (RFC 8288, section 3.3).
+            if name.eq_ignore_ascii_case("rel") && rel.is_none() {
+                rel = value;
+            }
+        }
+
+        for rel in rel.iter().flat_map(|rel| rel.split_whitespace()) {
+            relations.insert(rel, url);
+        }
+
+        // Skip anything after the parameters up to the next link.
+        match rest.find(',') {
+            Some(comma) => rest = &rest[comma..],
+            None => return,
+        }
+    }
+}
+
+// `name[=value]`, with the value a token or a quoted string. Returns the rest
+// of the input after the parameter.
+fn parse_param(input: &str) -> (&str, Option<String>, &str) {
+    let end = input.find(['=', ';', ',']).unwrap_or(input.len());
+    let name = input[..end].trim();
+    let Some(after) = input[end..].strip_prefix('=') else {
+        return (name, None, &input[end..]);
+    };
+
+    let after = after.trim_start();
+    let Some(quoted) = after.strip_prefix('"') else {
+        let end = after.find([';', ',']).unwrap_or(after.len());
+        return (name, Some(after[..end].trim().to_string()), &after[end..]);
+    };
+
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return (name, Some(value), &quoted[i + 1..]),
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    value.push(escaped);
+                }
+            }
+            _ => value.push(c),
+        }
+    }
+    // Unterminated quote: take what is there.
+    (name, Some(value), "")
+}
+
+#[cfg(test)]
+#[path = "link_header_tests.rs"]
+mod link_header_tests;
@@ -0,0 +1,56 @@
+use super::*;
+
+#[test]
+fn give_github_style_link_header_when_parse_link_header_then_every_relation_should_be_found() {
+    // give
+    let value = concat!(
+        r#"<https://api.example.com/repos?page=3&per_page=50>; rel="next", "#,
+        r#"<https://api.example.com/repos?page=1&per_page=50>; rel="prev", "#,
+        r#"<https://api.example.com/repos?page=1&per_page=50>; rel="first", "#,
+        r#"<https://api.example.com/repos?page=9&per_page=50>; rel="last""#,
+    );
+
+    // when
+    let links = parse_link_header(value);
+
+    // then
+    assert_eq!(links.next.as_deref(), Some("https://api.example.com/repos?page=3&per_page=50"));
+    assert_eq!(links.prev.as_deref(), Some("https://api.example.com/repos?page=1&per_page=50"));
+    assert_eq!(links.first.as_deref(), Some("https://api.example.com/repos?page=1&per_page=50"));
+    assert_eq!(links.last.as_deref(), Some("https://api.example.com/repos?page=9&per_page=50"));
+    assert!(links.other.is_empty());
+}
+
+#[test]
+fn give_quoted_params_and_multiple_rels_when_parse_link_header_then_commas_in_quotes_should_not_split() {
+    // give
+    let value = concat!(
+        r#"</items?page=2>; title="Next, please \"now\""; rel="next last", "#,
+        r#"</docs>;rel=Help;type="text/html", "#,
+        r#"</nowhere>; title="no rel""#,
+    );
+
+    // when
+    let links = parse_link_header(value);
+
+    // then
+    assert_eq!(links.next.as_deref(), Some("/items?page=2"));
+    assert_eq!(links.last.as_deref(), Some("/items?page=2"));
+    assert_eq!(links.get("help"), Some("/docs"));
+    assert_eq!(links.get("HELP"), Some("/docs"));
+    assert_eq!(links.other.len(), 1);
+    assert_eq!(links.first, None);
+}
+
+#[test]
+fn give_malformed_parts_when_parse_link_header_then_valid_links_should_still_be_returned() {
+    // give
+    let value = r#"garbage, <https://a.example/2>; rel=next, <https://a.example/unterminated"#;
+
+    // when
+    let links = parse_link_header(value);
+
+    // then
+    assert_eq!(links.next.as_deref(), Some("https://a.example/2"));
+    assert_eq!(links.last, None);
+}
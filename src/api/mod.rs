@@ -1,2 +1,36 @@
 pub mod api_client;
+pub mod api_error;
+pub mod api_response;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod body_stream;
+pub mod clock;
+pub mod composed_request;
+pub mod field_naming;
+pub mod header_pairs;
+mod json_array;
+mod json_rpc;
+pub mod link_header;
+pub mod prefer;
+mod query;
+pub mod rate_limit;
+mod request_dump;
+pub mod request_options;
+pub mod retry_policy;
+pub(crate) mod runtime;
 pub use api_client::ApiClient;
+#[cfg(not(target_arch = "wasm32"))]
+pub use api_client::{Cassette, CassetteMode, HttpVersion};
+pub use api_error::ApiError;
+pub use api_response::ApiResponse;
+#[cfg(not(target_arch = "wasm32"))]
+pub use body_stream::BodyStream;
+pub use clock::{Clock, SystemClock};
+pub use composed_request::ComposedRequest;
+pub use field_naming::FieldNaming;
+pub use header_pairs::HeaderPairs;
+pub use link_header::{LinkRelations, parse_link_header};
+pub use prefer::Prefer;
+pub use query::{ArrayFormat, SpaceEncoding};
+pub use rate_limit::RateLimit;
+pub use request_options::RequestOptions;
+pub use retry_policy::{JitterKind, RetryPolicy};
@@ -0,0 +1,24 @@
+use super::HeaderPairs;
+
+// `Prefer` header values (RFC 7240). Pass one as a request's extra headers;
+// what the server did is in ApiResponse::preference_applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prefer {
+    ReturnMinimal,
+    ReturnRepresentation,
+}
+
+impl Prefer {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Prefer::ReturnMinimal => "return=minimal",
+            Prefer::ReturnRepresentation => "return=representation",
+        }
+    }
+}
+
+impl HeaderPairs for Prefer {
+    fn header_pairs(&self) -> Vec<(&str, &str)> {
+        vec![("Prefer", self.as_str())]
+    }
+}
@@ -0,0 +1,121 @@
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::ApiError;
+
+// How a sequence field in a query struct is written out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrayFormat {
+    // ids=1&ids=2
+    #[default]
+    Repeat,
+    // ids[]=1&ids[]=2
+    Brackets,
+    // ids=1,2
+    Comma,
+}
+
+// How spaces in query keys and values are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpaceEncoding {
+    // a%20b
+    #[default]
+    Percent,
+    // a+b, as in HTML forms
+    Plus,
+}
+
+// Characters left alone by application/x-www-form-urlencoded, besides
+// alphanumerics.
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'*').remove(b'-').remove(b'.').remove(b'_');
+
+// Each key and value is encoded on its own; the `[]` suffix and the `,`
+// separator are written literally, since servers match on them before
+// decoding.
+pub(crate) fn query_string<T>(query: &T, format: ArrayFormat, spaces: SpaceEncoding) -> Result<String, ApiError>
+where
+    T: Serialize + ?Sized,
+{
+    let encode = |s: &str| {
+        let encoded = utf8_percent_encode(s, COMPONENT).to_string();
+        match spaces {
+            SpaceEncoding::Percent => encoded,
+            // A literal `%20` in the input has its `%` encoded, so every
+            // remaining one is a space.
+            SpaceEncoding::Plus => encoded.replace("%20", "+"),
+        }
+    };
+
+    let mut pairs = Vec::new();
+    for (name, values) in encode_query(query)? {
+        let name = encode(&name);
+        match (values, format) {
+            (QueryValue::Scalar(value), _) => pairs.push(format!("{name}={}", encode(&value))),
+            (QueryValue::List(items), ArrayFormat::Repeat) => {
+                pairs.extend(items.iter().map(|v| format!("{name}={}", encode(v))));
+            }
+            (QueryValue::List(items), ArrayFormat::Brackets) => {
+                pairs.extend(items.iter().map(|v| format!("{name}[]={}", encode(v))));
+            }
+            (QueryValue::List(items), ArrayFormat::Comma) => {
+                let joined = items.iter().map(|v| encode(v)).collect::<Vec<_>>().join(",");
+                pairs.push(format!("{name}={joined}"));
+            }
+        }
+    }
+
+    Ok(pairs.join("&"))
+}
+
+pub(crate) enum QueryValue {
+    Scalar(String),
+    List(Vec<String>),
+}
+
+// Flattens a struct or map of scalars and sequences into named values. None
+// fields are skipped; nested objects are rejected.
+pub(crate) fn encode_query<T>(query: &T) -> Result<Vec<(String, QueryValue)>, ApiError>
+where
+    T: Serialize + ?Sized,
+{
+    let invalid = |reason: String| ApiError::InvalidQuery { reason };
+
+    let fields = match serde_json::to_value(query).map_err(|e| invalid(e.to_string()))? {
+        Value::Object(fields) => fields,
+        other => return Err(invalid(format!("expected a struct or map, got {other}"))),
+    };
+
+    let mut values = Vec::new();
+    for (name, value) in fields {
+        match value {
+            Value::Null => {}
+            Value::Array(items) => {
+                let items = items
+                    .into_iter()
+                    .map(|item| scalar(item).ok_or_else(|| invalid(format!("{name} holds a nested value"))))
+                    .collect::<Result<Vec<_>, _>>()?;
+                values.push((name, QueryValue::List(items)));
+            }
+            value => {
+                let value = scalar(value).ok_or_else(|| invalid(format!("{name} holds a nested value")))?;
+                values.push((name, QueryValue::Scalar(value)));
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+fn scalar(value: Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[path = "query_tests.rs"]
+mod query_tests;
@@ -0,0 +1,106 @@
+use serde::Serialize;
+
+use super::*;
+
+#[derive(Serialize)]
+struct Filter {
+    ids: Vec<u32>,
+    status: &'static str,
+    tag: Option<&'static str>,
+}
+
+fn filter() -> Filter {
+    Filter {
+        ids: vec![1, 2, 3],
+        status: "open",
+        tag: None,
+    }
+}
+
+fn filter_query(format: ArrayFormat) -> String {
+    query_string(&filter(), format, SpaceEncoding::Percent).unwrap()
+}
+
+#[test]
+fn give_repeat_format_when_encode_query_then_key_should_repeat_per_element() {
+    // give / when
+    let query = filter_query(ArrayFormat::Repeat);
+
+    // then
+    assert_eq!(query, "ids=1&ids=2&ids=3&status=open");
+}
+
+#[test]
+fn give_brackets_format_when_encode_query_then_keys_should_end_in_brackets() {
+    // give / when
+    let query = filter_query(ArrayFormat::Brackets);
+
+    // then
+    assert_eq!(query, "ids[]=1&ids[]=2&ids[]=3&status=open");
+}
+
+#[test]
+fn give_comma_format_when_encode_query_then_elements_should_be_joined() {
+    // give / when
+    let query = filter_query(ArrayFormat::Comma);
+
+    // then
+    assert_eq!(query, "ids=1,2,3&status=open");
+}
+
+#[test]
+fn give_nested_object_when_encode_query_then_should_return_invalid_query() {
+    // give
+    let query = serde_json::json!({ "filter": { "status": "open" } });
+
+    // when
+    let result = query_string(&query, ArrayFormat::Repeat, SpaceEncoding::Percent);
+
+    // then
+    assert!(matches!(result, Err(ApiError::InvalidQuery { .. })));
+}
+
+#[derive(Serialize)]
+struct Search {
+    q: &'static str,
+    tags: Vec<&'static str>,
+}
+
+fn search() -> Search {
+    Search {
+        q: "red shoes+laces",
+        tags: vec!["on sale"],
+    }
+}
+
+#[test]
+fn give_percent_space_encoding_when_query_string_then_spaces_should_be_percent_20() {
+    // give / when
+    let query = query_string(&search(), ArrayFormat::Repeat, SpaceEncoding::Percent).unwrap();
+
+    // then
+    assert_eq!(query, "q=red%20shoes%2Blaces&tags=on%20sale");
+}
+
+#[test]
+fn give_plus_space_encoding_when_query_string_then_spaces_should_be_plus() {
+    // give / when
+    let query = query_string(&search(), ArrayFormat::Repeat, SpaceEncoding::Plus).unwrap();
+
+    // then
+    assert_eq!(query, "q=red+shoes%2Blaces&tags=on+sale");
+}
+
+#[test]
+fn give_reserved_characters_when_query_string_then_they_should_be_encoded_around_literal_separators() {
+    // give
+    let query = serde_json::json!({ "a[]": ["x,y", "&"] });
+
+    // when
+    let brackets = query_string(&query, ArrayFormat::Brackets, SpaceEncoding::Percent).unwrap();
+    let comma = query_string(&query, ArrayFormat::Comma, SpaceEncoding::Percent).unwrap();
+
+    // then
+    assert_eq!(brackets, "a%5B%5D[]=x%2Cy&a%5B%5D[]=%26");
+    assert_eq!(comma, "a%5B%5D=x%2Cy,%26");
+}
@@ -0,0 +1,33 @@
+use reqwest::header::HeaderMap;
+
+// Rate-limit state advertised by the server. Both the common
+// `X-RateLimit-*` headers and the unprefixed `RateLimit-*` ones are read;
+// anything missing or unparsable is None.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    pub reset: Option<u64>,
+}
+
+impl RateLimit {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            limit: read(headers, "limit"),
+            remaining: read(headers, "remaining"),
+            reset: read(headers, "reset"),
+        }
+    }
+}
+
+fn read(headers: &HeaderMap, field: &str) -> Option<u64> {
+    [format!("x-ratelimit-{field}"), format!("ratelimit-{field}")]
+        .iter()
+        .find_map(|name| headers.get(name.as_str()))
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+#[cfg(test)]
+#[path = "rate_limit_tests.rs"]
+mod rate_limit_tests;
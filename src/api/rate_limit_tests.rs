@@ -0,0 +1,35 @@
+use super::*;
+use reqwest::header::HeaderValue;
+
+#[test]
+fn give_prefixed_headers_when_from_headers_then_fields_should_be_parsed() {
+    // give
+    let mut headers = HeaderMap::new();
+    headers.insert("X-RateLimit-Limit", HeaderValue::from_static("100"));
+    headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("42"));
+    headers.insert("X-RateLimit-Reset", HeaderValue::from_static("1700000000"));
+
+    // when
+    let limit = RateLimit::from_headers(&headers);
+
+    // then
+    assert_eq!(limit.limit, Some(100));
+    assert_eq!(limit.remaining, Some(42));
+    assert_eq!(limit.reset, Some(1_700_000_000));
+}
+
+#[test]
+fn give_unprefixed_or_missing_headers_when_from_headers_then_should_fall_back_or_be_none() {
+    // give
+    let mut headers = HeaderMap::new();
+    headers.insert("RateLimit-Remaining", HeaderValue::from_static("7"));
+    headers.insert("X-RateLimit-Reset", HeaderValue::from_static("soon"));
+
+    // when
+    let limit = RateLimit::from_headers(&headers);
+
+    // then
+    assert_eq!(limit.limit, None);
+    assert_eq!(limit.remaining, Some(7));
+    assert_eq!(limit.reset, None);
+}
@@ -0,0 +1,54 @@
+use std::fmt::Write;
+
+use reqwest::Request;
+use reqwest::header::{AUTHORIZATION, HeaderMap, PROXY_AUTHORIZATION};
+
+use super::ApiResponse;
+
+// curl -v style: `>` lines for what went out, `<` lines for what came back.
+// `secret_param` names a query parameter whose value is redacted.
+pub(crate) fn format_request(request: &Request, secret_param: Option<&str>) -> String {
+    let mut url = request.url().clone();
+    if let Some(secret) = secret_param.filter(|s| url.query_pairs().any(|(k, _)| k == *s)) {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(k, v)| {
+                let v = if k == secret { "[redacted]".into() } else { v };
+                (k.into_owned(), v.into_owned())
+            })
+            .collect();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+
+    let mut out = format!("> {} {}\n", request.method(), url);
+    write_headers(&mut out, '>', request.headers());
+    out.push_str(">\n");
+    if let Some(body) = request.body().and_then(|b| b.as_bytes()) {
+        out.push_str(&String::from_utf8_lossy(body));
+        out.push('\n');
+    }
+    out
+}
+
+pub(crate) fn format_response(resp: &ApiResponse) -> String {
+    let mut out = format!("< {}\n", resp.status);
+    write_headers(&mut out, '<', &resp.headers);
+    out.push_str("<\n");
+    if !resp.body.is_empty() {
+        out.push_str(&String::from_utf8_lossy(&resp.body));
+        out.push('\n');
+    }
+    out
+}
+
+fn write_headers(out: &mut String, marker: char, headers: &HeaderMap) {
+    for (name, value) in headers {
+        let value = if value.is_sensitive() || name == AUTHORIZATION || name == PROXY_AUTHORIZATION
+        {
+            "[redacted]"
+        } else {
+            value.to_str().unwrap_or("[binary]")
+        };
+        let _ = writeln!(out, "{marker} {name}: {value}");
+    }
+}
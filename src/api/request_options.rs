@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use super::api_client::IDEMPOTENCY_KEY;
+
+// Per-call settings for the `*_with_options` methods. Anything left unset
+// falls back to the client's configuration, so `None` or
+// `RequestOptions::default()` behaves exactly like the plain method.
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) token: Option<String>,
+    headers: Vec<(String, String)>,
+    idempotency_key: Option<String>,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Replaces the client and endpoint timeouts for this call.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    // Authenticates this call with `token` instead of the client token,
+    // using the client's scheme or query parameter.
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    // Added after the client's default headers, replacing any of the same
+    // name.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    // Sent as `Idempotency-Key`, which also makes the call eligible for
+    // retries under a retry predicate that checks for it.
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    pub(crate) fn header_pairs(&self) -> Vec<(&str, &str)> {
+        let key = self.idempotency_key.as_deref().map(|key| (IDEMPOTENCY_KEY, key));
+        self.headers
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .chain(key)
+            .collect()
+    }
+}
@@ -0,0 +1,169 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use super::ApiError;
+
+// Randomizes backoff delays so clients that failed together do not retry in
+// lockstep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JitterKind {
+    #[default]
+    None,
+    // Anywhere between zero and the backoff delay.
+    Full,
+    // Half the backoff delay plus a random share of the other half.
+    Equal,
+}
+
+// Exponential backoff: the n-th retry waits base_delay * 2^(n - 1), capped at
+// max_delay, then jittered. Transport errors are retried by kind (connect
+// and timeout by default, see `retry_request`); statuses follow
+// `retry_statuses`, or 5xx and 429 when it is not set. `max_elapsed` bounds
+// the whole sequence, backoff sleeps included.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub retry_statuses: Option<Vec<u16>>,
+    pub max_elapsed: Option<Duration>,
+    pub jitter: JitterKind,
+    pub retry_connect: bool,
+    pub retry_timeout: bool,
+    // Other failures to get a response, such as a connection closed before
+    // the response arrived. Off by default on native targets; on wasm the
+    // fetch backend reports every network failure this way, so it is on.
+    pub retry_request: bool,
+    // Shared by clones, so every client built from one policy draws from the
+    // same sequence.
+    rng: Arc<Mutex<fastrand::Rng>>,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, base_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base_delay,
+            max_delay: Duration::from_secs(30),
+            retry_statuses: None,
+            max_elapsed: None,
+            jitter: JitterKind::None,
+            retry_connect: true,
+            retry_timeout: true,
+            retry_request: cfg!(target_arch = "wasm32"),
+            rng: Arc::new(Mutex::new(fastrand::Rng::new())),
+        }
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_retry_on_statuses(mut self, statuses: &[u16]) -> Self {
+        self.retry_statuses = Some(statuses.to_vec());
+        self
+    }
+
+    pub fn with_max_retry_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    pub fn with_retry_on_connect(mut self, enabled: bool) -> Self {
+        self.retry_connect = enabled;
+        self
+    }
+
+    pub fn with_retry_on_timeout(mut self, enabled: bool) -> Self {
+        self.retry_timeout = enabled;
+        self
+    }
+
+    pub fn with_retry_on_request(mut self, enabled: bool) -> Self {
+        self.retry_request = enabled;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: JitterKind) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    // Makes the jittered delays reproducible, for tests.
+    pub fn with_jitter_seed(mut self, seed: u64) -> Self {
+        self.rng = Arc::new(Mutex::new(fastrand::Rng::with_seed(seed)));
+        self
+    }
+
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        match self.jitter {
+            JitterKind::None => delay,
+            JitterKind::Full => self.random_up_to(delay),
+            JitterKind::Equal => delay / 2 + self.random_up_to(delay - delay / 2),
+        }
+    }
+
+    fn random_up_to(&self, max: Duration) -> Duration {
+        let max = u64::try_from(max.as_nanos()).unwrap_or(u64::MAX);
+        let mut rng = self.rng.lock().unwrap_or_else(PoisonError::into_inner);
+        Duration::from_nanos(rng.u64(0..=max))
+    }
+
+    pub(crate) fn within_budget(&self, elapsed: Duration) -> bool {
+        self.max_elapsed.is_none_or(|max| elapsed <= max)
+    }
+
+    pub(crate) fn retries_status(&self, status: StatusCode) -> bool {
+        match &self.retry_statuses {
+            Some(statuses) => statuses.contains(&status.as_u16()),
+            None => status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    // reqwest reports connect failures and timeouts as request errors too,
+    // so `retry_request` only covers what is neither.
+    fn retries_transport(&self, err: &reqwest::Error) -> bool {
+        if is_connect(err) {
+            self.retry_connect
+        } else if err.is_timeout() {
+            self.retry_timeout
+        } else {
+            self.retry_request && err.is_request()
+        }
+    }
+
+    pub(crate) fn retries_error(&self, err: &ApiError) -> bool {
+        match err {
+            ApiError::Http(err) => self.retries_transport(err),
+            ApiError::Status { status, .. } => self.retries_status(*status),
+            ApiError::Unauthorized { .. } => self.retries_status(StatusCode::UNAUTHORIZED),
+            ApiError::Forbidden { .. } => self.retries_status(StatusCode::FORBIDDEN),
+            ApiError::PreconditionFailed { .. } => {
+                self.retries_status(StatusCode::PRECONDITION_FAILED)
+            }
+            ApiError::RateLimited { .. } => self.retries_status(StatusCode::TOO_MANY_REQUESTS),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_connect(err: &reqwest::Error) -> bool {
+    err.is_connect()
+}
+
+// The fetch backend cannot tell connect failures apart; they arrive as
+// request errors.
+#[cfg(target_arch = "wasm32")]
+fn is_connect(_err: &reqwest::Error) -> bool {
+    false
+}
+
+#[cfg(test)]
+#[path = "retry_policy_tests.rs"]
+mod retry_policy_tests;
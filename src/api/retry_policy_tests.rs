@@ -0,0 +1,99 @@
+use super::*;
+
+#[test]
+fn give_base_delay_when_delay_for_then_delay_should_double_per_retry() {
+    // give
+    let policy = RetryPolicy::new(5, Duration::from_millis(100));
+
+    // when / then
+    assert_eq!(policy.delay_for(1), Duration::from_millis(100));
+    assert_eq!(policy.delay_for(2), Duration::from_millis(200));
+    assert_eq!(policy.delay_for(3), Duration::from_millis(400));
+}
+
+#[test]
+fn give_max_delay_when_delay_for_then_delay_should_be_capped() {
+    // give
+    let policy =
+        RetryPolicy::new(50, Duration::from_secs(1)).with_max_delay(Duration::from_secs(5));
+
+    // when / then
+    assert_eq!(policy.delay_for(4), Duration::from_secs(5));
+    assert_eq!(policy.delay_for(40), Duration::from_secs(5));
+}
+
+#[test]
+fn give_status_codes_when_retries_status_then_only_5xx_and_429_should_retry() {
+    // give
+    let policy = RetryPolicy::new(3, Duration::ZERO);
+
+    // when / then
+    assert!(policy.retries_status(StatusCode::INTERNAL_SERVER_ERROR));
+    assert!(policy.retries_status(StatusCode::SERVICE_UNAVAILABLE));
+    assert!(policy.retries_status(StatusCode::TOO_MANY_REQUESTS));
+    assert!(!policy.retries_status(StatusCode::NOT_FOUND));
+    assert!(!policy.retries_status(StatusCode::CONFLICT));
+    assert!(!policy.retries_status(StatusCode::OK));
+}
+
+#[test]
+fn give_explicit_statuses_when_retries_status_then_only_listed_codes_should_retry() {
+    // give
+    let policy = RetryPolicy::new(3, Duration::ZERO).with_retry_on_statuses(&[503, 408]);
+
+    // when / then
+    assert!(policy.retries_status(StatusCode::SERVICE_UNAVAILABLE));
+    assert!(policy.retries_status(StatusCode::REQUEST_TIMEOUT));
+    assert!(!policy.retries_status(StatusCode::INTERNAL_SERVER_ERROR));
+    assert!(!policy.retries_status(StatusCode::TOO_MANY_REQUESTS));
+    assert!(!policy.retries_status(StatusCode::CONFLICT));
+}
+
+#[test]
+fn give_max_elapsed_when_within_budget_then_should_allow_up_to_the_cap() {
+    // give
+    let unbounded = RetryPolicy::new(3, Duration::ZERO);
+    let bounded = RetryPolicy::new(3, Duration::ZERO).with_max_retry_elapsed(Duration::from_secs(5));
+
+    // when / then
+    assert!(unbounded.within_budget(Duration::from_secs(3600)));
+    assert!(bounded.within_budget(Duration::from_secs(5)));
+    assert!(!bounded.within_budget(Duration::from_millis(5001)));
+}
+
+#[test]
+fn give_seeded_jitter_when_delay_for_then_delays_should_be_reproducible_and_bounded() {
+    // give
+    let policy = || {
+        RetryPolicy::new(5, Duration::from_millis(100))
+            .with_jitter(JitterKind::Full)
+            .with_jitter_seed(42)
+    };
+    let (first, second) = (policy(), policy());
+
+    // when
+    let a: Vec<Duration> = (1..=4).map(|retry| first.delay_for(retry)).collect();
+    let b: Vec<Duration> = (1..=4).map(|retry| second.delay_for(retry)).collect();
+
+    // then
+    assert_eq!(a, b);
+    for (retry, delay) in (1..=4).zip(&a) {
+        assert!(*delay <= Duration::from_millis(100 << (retry - 1)), "{delay:?}");
+    }
+    assert_ne!(a[3], Duration::from_millis(800));
+}
+
+#[test]
+fn give_equal_jitter_when_delay_for_then_delay_should_be_at_least_half_the_backoff() {
+    // give
+    let policy = RetryPolicy::new(5, Duration::from_millis(100))
+        .with_jitter(JitterKind::Equal)
+        .with_jitter_seed(7);
+
+    // when / then
+    for _ in 0..50 {
+        let delay = policy.delay_for(3);
+        assert!(delay >= Duration::from_millis(200), "{delay:?}");
+        assert!(delay <= Duration::from_millis(400), "{delay:?}");
+    }
+}
@@ -1,4 +1,6 @@
 pub mod api;
 pub mod models;
 pub mod repository;
-pub mod service;  
\ No newline at end of file
+pub mod service;
+#[cfg(all(feature = "test-util", not(target_arch = "wasm32")))]
+pub mod testkit;
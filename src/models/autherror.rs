@@ -0,0 +1,18 @@
+use thiserror::Error;
+
+use crate::api::ApiError;
+
+#[derive(Debug, Error)]
+pub enum AuthError {
+    #[error("auth request failed")]
+    Api(#[from] ApiError),
+
+    #[error("auth repository failed")]
+    Repository(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("token has an empty access_token")]
+    MissingAccessToken,
+
+    #[error("unsupported token_type: {token_type:?}")]
+    UnsupportedTokenType { token_type: String },
+}
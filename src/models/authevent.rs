@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+// Token lifecycle events reported to an AuthService event listener. `at` is
+// wall-clock time since the Unix epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthEvent {
+    TokenIssued { at: Duration },
+    // A new token replacing one that expired.
+    TokenRefreshed { at: Duration },
+    AuthFailed { at: Duration, reason: String },
+}
+
+impl AuthEvent {
+    pub fn at(&self) -> Duration {
+        match self {
+            AuthEvent::TokenIssued { at }
+            | AuthEvent::TokenRefreshed { at }
+            | AuthEvent::AuthFailed { at, .. } => *at,
+        }
+    }
+}
@@ -1,5 +1,18 @@
+use std::time::Duration;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "chrono")]
+use crate::api::runtime::unix_now;
+
+use super::AuthError;
+
+// Token types this crate knows how to send (RFC 6749 makes the comparison
+// case-insensitive).
+const KNOWN_TOKEN_TYPES: [&str; 3] = ["Bearer", "DPoP", "MAC"];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthToken {
     pub access_token: String,
@@ -7,4 +20,63 @@ pub struct AuthToken {
     pub expires_in: Option<u64>,
     pub refresh_token: Option<String>,
     pub scope: Option<String>,
+    // When the token was received: stamped on deserialization, and by the
+    // auth repositories from their client's clock. It is serialized, so a
+    // cached token reloaded later keeps its original expiry.
+    #[cfg(feature = "chrono")]
+    #[serde(default = "received_now")]
+    pub obtained_at: DateTime<Utc>,
 }
+
+impl AuthToken {
+    pub fn validate(&self) -> Result<(), AuthError> {
+        if self.access_token.trim().is_empty() {
+            return Err(AuthError::MissingAccessToken);
+        }
+
+        let token_type = self.token_type.trim();
+        if !KNOWN_TOKEN_TYPES
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(token_type))
+        {
+            return Err(AuthError::UnsupportedTokenType {
+                token_type: self.token_type.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // `obtained_at + expires_in`, or None when the server sent no expiry.
+    #[cfg(feature = "chrono")]
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        let lifetime = TimeDelta::try_seconds(i64::try_from(self.expires_in?).ok()?)?;
+        self.obtained_at.checked_add_signed(lifetime)
+    }
+
+    // Sets obtained_at to `now` (time since the Unix epoch). Without the
+    // chrono feature there is nothing to record.
+    #[cfg_attr(not(feature = "chrono"), allow(unused_mut, unused_variables))]
+    pub(crate) fn received_at(mut self, now: Duration) -> Self {
+        #[cfg(feature = "chrono")]
+        {
+            self.obtained_at = timestamp(now);
+        }
+        self
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn received_now() -> DateTime<Utc> {
+    timestamp(unix_now())
+}
+
+#[cfg(feature = "chrono")]
+fn timestamp(since_epoch: Duration) -> DateTime<Utc> {
+    let secs = i64::try_from(since_epoch.as_secs()).unwrap_or(i64::MAX);
+    DateTime::from_timestamp(secs, since_epoch.subsec_nanos()).unwrap_or_default()
+}
+
+#[cfg(test)]
+#[path = "authtoken_tests.rs"]
+mod authtoken_tests;
@@ -0,0 +1,110 @@
+use super::*;
+
+fn token(access_token: &str, token_type: &str) -> AuthToken {
+    AuthToken {
+        access_token: access_token.into(),
+        token_type: token_type.into(),
+        expires_in: Some(3600),
+        refresh_token: None,
+        scope: None,
+        #[cfg(feature = "chrono")]
+        obtained_at: Default::default(),
+    }
+}
+
+#[test]
+fn give_bearer_token_when_validate_then_should_be_ok() {
+    // give
+    let tokens = [token("abc123", "Bearer"), token("abc123", "bearer")];
+
+    // when / then
+    for token in tokens {
+        assert!(token.validate().is_ok(), "{token:?}");
+    }
+}
+
+#[test]
+fn give_empty_access_token_when_validate_then_missing_access_token_should_be_returned() {
+    // give
+    let token = token("  ", "Bearer");
+
+    // when
+    let result = token.validate();
+
+    // then
+    assert!(matches!(result, Err(AuthError::MissingAccessToken)));
+}
+
+#[test]
+fn give_unknown_or_empty_token_type_when_validate_then_unsupported_token_type_should_be_returned() {
+    // give
+    let unknown = token("abc123", "Magic");
+    let empty = token("abc123", "");
+
+    // when / then
+    assert!(matches!(
+        unknown.validate(),
+        Err(AuthError::UnsupportedTokenType { token_type }) if token_type == "Magic"
+    ));
+    assert!(matches!(empty.validate(), Err(AuthError::UnsupportedTokenType { .. })));
+    assert_eq!(
+        unknown.validate().unwrap_err().to_string(),
+        r#"unsupported token_type: "Magic""#
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn give_token_response_when_deserialized_then_expires_at_should_be_about_now_plus_expires_in() {
+    // give
+    let json = r#"{"access_token":"abc123","token_type":"Bearer","expires_in":3600}"#;
+    let expected = timestamp(unix_now() + std::time::Duration::from_secs(3600));
+
+    // when
+    let token: AuthToken = serde_json::from_str(json).unwrap();
+    let expires_at = token.expires_at().expect("expires_at expected");
+
+    // then
+    let drift = (expires_at - expected).num_seconds().abs();
+    assert!(drift <= 5, "expires_at {expires_at} is {drift}s away from {expected}");
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn give_token_received_at_fixed_time_when_expires_at_then_received_at_plus_expires_in_should_be_returned() {
+    // give
+    let token = token("abc123", "Bearer").received_at(std::time::Duration::from_secs(1_700_000_000));
+
+    // when
+    let expires_at = token.expires_at().expect("expires_at expected");
+
+    // then
+    assert_eq!(expires_at, DateTime::from_timestamp(1_700_003_600, 0).unwrap());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn give_cached_token_when_round_tripped_through_json_then_expires_at_should_not_move() {
+    // give
+    let token = token("abc123", "Bearer").received_at(std::time::Duration::from_secs(1_700_000_000));
+    let before = token.expires_at();
+
+    // when
+    let reloaded: AuthToken = serde_json::from_str(&serde_json::to_string(&token).unwrap()).unwrap();
+
+    // then
+    assert_eq!(reloaded.expires_at(), before);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn give_token_without_expires_in_when_expires_at_then_none_should_be_returned() {
+    // give
+    let token = AuthToken {
+        expires_in: None,
+        ..token("abc123", "Bearer")
+    };
+
+    // when / then
+    assert!(token.expires_at().is_none());
+}
@@ -0,0 +1,12 @@
+use reqwest::StatusCode;
+
+use super::AuthToken;
+
+// Outcome of a login attempt that reached the server. `token` is set on
+// success; `body` holds the raw response body when the server refused.
+#[derive(Debug, Clone)]
+pub struct LoginResult {
+    pub status: StatusCode,
+    pub token: Option<AuthToken>,
+    pub body: Option<String>,
+}
@@ -1,2 +1,8 @@
+pub mod autherror;
+pub mod authevent;
 pub mod authtoken;
-pub use authtoken::AuthToken;
\ No newline at end of file
+pub mod loginresult;
+pub use autherror::AuthError;
+pub use authevent::AuthEvent;
+pub use authtoken::AuthToken;
+pub use loginresult::LoginResult;
@@ -0,0 +1,57 @@
+use crate::api::ApiClient;
+use crate::models::AuthToken;
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ApiKeyRepository {
+    async fn exchange(
+        &self,
+        api_key: &str,
+    ) -> Result<AuthToken, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+pub struct ApiKeyAuthRepository {
+    client: ApiClient,
+    auth_path: String,
+    key_field: String,
+}
+
+impl ApiKeyAuthRepository {
+    pub fn new(base_url: &str, auth_path: &str) -> Self {
+        Self {
+            client: ApiClient::new(base_url),
+            auth_path: auth_path.to_string(),
+            key_field: "api_key".to_string(),
+        }
+    }
+
+    pub fn with_key_field(mut self, key_field: &str) -> Self {
+        self.key_field = key_field.to_string();
+        self
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ApiKeyRepository for ApiKeyAuthRepository {
+    async fn exchange(
+        &self,
+        api_key: &str,
+    ) -> Result<AuthToken, Box<dyn std::error::Error + Send + Sync>> {
+        let mut body = Map::new();
+        body.insert(self.key_field.clone(), Value::from(api_key));
+
+        let token: AuthToken = self
+            .client
+            .post_json(&self.auth_path, &body, None)
+            .await?;
+
+        Ok(token.received_at(self.client.unix_now()))
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+#[path = "api_key_repository_tests.rs"]
+mod api_key_repository_tests;
@@ -0,0 +1,92 @@
+use super::*;
+use httpmock::prelude::*;
+
+#[tokio::test]
+async fn give_valid_api_key_when_exchange_then_token_should_be_returned() {
+    let server = MockServer::start();
+    let auth_path = "/auth/api-key";
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(auth_path)
+            .header("content-type", "application/json")
+            .json_body(serde_json::json!({ "api_key": "long-lived-key" }));
+        then.status(200).json_body_obj(&AuthToken {
+            access_token: "short-lived".into(),
+            token_type: "Bearer".into(),
+            expires_in: Some(900),
+            refresh_token: None,
+            scope: None,
+            #[cfg(feature = "chrono")]
+            obtained_at: Default::default(),
+        });
+    });
+
+    // give
+    let repo = ApiKeyAuthRepository::new(&server.base_url(), auth_path);
+
+    // when
+    let token = repo
+        .exchange("long-lived-key")
+        .await
+        .expect("token expected");
+
+    // then
+    mock.assert();
+    assert_eq!(token.access_token, "short-lived");
+    assert_eq!(token.expires_in, Some(900));
+}
+
+#[tokio::test]
+async fn give_custom_key_field_when_exchange_then_field_name_should_be_used() {
+    let server = MockServer::start();
+    let auth_path = "/v1/tokens";
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(auth_path)
+            .json_body(serde_json::json!({ "key": "long-lived-key" }));
+        then.status(200).json_body(serde_json::json!({
+            "access_token": "short-lived",
+            "token_type": "Bearer"
+        }));
+    });
+
+    // give
+    let repo = ApiKeyAuthRepository::new(&server.base_url(), auth_path).with_key_field("key");
+
+    // when
+    let token = repo
+        .exchange("long-lived-key")
+        .await
+        .expect("token expected");
+
+    // then
+    mock.assert();
+    assert_eq!(token.access_token, "short-lived");
+}
+
+#[tokio::test]
+async fn give_rejected_api_key_when_exchange_then_error_should_be_propagated() {
+    let server = MockServer::start();
+    let auth_path = "/auth/api-key";
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(auth_path)
+            .json_body(serde_json::json!({ "api_key": "revoked" }));
+        then.status(403).json_body(serde_json::json!({
+            "error": "invalid_api_key"
+        }));
+    });
+
+    // give
+    let repo = ApiKeyAuthRepository::new(&server.base_url(), auth_path);
+
+    // when
+    let result = repo.exchange("revoked").await;
+
+    // then
+    mock.assert();
+    assert!(result.is_err(), "expected rejected key to fail");
+}
@@ -1,20 +1,73 @@
-use crate::api::ApiClient;
-use crate::models::AuthToken;
+use crate::api::runtime::unix_now;
+use crate::api::{ApiClient, ApiError, ApiResponse, FieldNaming};
+use crate::models::{AuthError, AuthToken, LoginResult};
 use async_trait::async_trait;
-use serde::Serialize;
+use std::time::Duration;
+use reqwest::{Method, StatusCode};
+use reqwest::header::{ACCEPT, HeaderMap};
 
-#[async_trait]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 pub trait AuthRepository {
     async fn authenticate(
         &self,
         client_id: &str,
         client_secret: &str,
     ) -> Result<AuthToken, Box<dyn std::error::Error + Send + Sync>>;
+
+    // Repositories that see the HTTP exchange should override this to report
+    // the real status and the body of a refusal. The default can only tell
+    // success from failure.
+    async fn authenticate_detailed(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<LoginResult, AuthError>
+    where
+        Self: Sync,
+    {
+        let token = self
+            .authenticate(client_id, client_secret)
+            .await
+            .map_err(AuthError::Repository)?;
+
+        Ok(LoginResult {
+            status: StatusCode::OK,
+            token: Some(token),
+            body: None,
+        })
+    }
+
+    // Ok(true) when the credentials are accepted, Ok(false) on a clean 401.
+    // Other refusals become the same ApiError variants as any client call
+    // (Forbidden, RateLimited...). The default builds them from
+    // authenticate_detailed, which carries no response headers.
+    async fn verify(&self, client_id: &str, client_secret: &str) -> Result<bool, AuthError>
+    where
+        Self: Sync,
+    {
+        let result = self.authenticate_detailed(client_id, client_secret).await?;
+        if result.token.is_some() {
+            return Ok(true);
+        }
+        if result.status == StatusCode::UNAUTHORIZED {
+            return Ok(false);
+        }
+
+        let resp = ApiResponse {
+            status: result.status,
+            headers: HeaderMap::new(),
+            body: result.body.unwrap_or_default().into(),
+            final_url: String::new(),
+        };
+        Err(resp.into_status_error(unix_now()).into())
+    }
 }
 
 pub struct RestAuthRepository {
     client: ApiClient,
     auth_path: String,
+    form_fields: Option<(String, String)>,
 }
 
 impl RestAuthRepository {
@@ -22,37 +75,131 @@ impl RestAuthRepository {
         Self {
             client: ApiClient::new(base_url),
             auth_path: auth_path.to_string(),
+            form_fields: None,
+        }
+    }
+
+    // Bounds each token request, so a stuck endpoint fails the login instead
+    // of hanging it.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client = self.client.with_timeout(timeout);
+        self
+    }
+
+    // Any other option of the internal client (retries, default headers...):
+    // `configure` receives it and returns the client to use.
+    pub fn with_client_options(mut self, configure: impl FnOnce(ApiClient) -> ApiClient) -> Self {
+        self.client = configure(self.client);
+        self
+    }
+
+    pub fn with_field_naming(mut self, naming: FieldNaming) -> Self {
+        self.client = self.client.with_field_naming(naming);
+        self
+    }
+
+    // Content-Type of the credentials form, for servers that want a charset:
+    // "application/x-www-form-urlencoded; charset=utf-8".
+    pub fn with_form_content_type(mut self, content_type: &str) -> Self {
+        self.client = self.client.with_form_content_type(content_type);
+        self
+    }
+
+    pub fn with_form_fields(mut self, client_id_field: &str, client_secret_field: &str) -> Self {
+        self.form_fields = Some((client_id_field.to_string(), client_secret_field.to_string()));
+        self
+    }
+
+    fn form_field_names(&self) -> (String, String) {
+        match &self.form_fields {
+            Some((id_field, secret_field)) => (id_field.clone(), secret_field.clone()),
+            None => {
+                let naming = self.client.field_naming();
+                (naming.apply("client_id"), naming.apply("client_secret"))
+            }
         }
     }
 }
 
-#[async_trait]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl AuthRepository for RestAuthRepository {
     async fn authenticate(
         &self,
         client_id: &str,
         client_secret: &str,
     ) -> Result<AuthToken, Box<dyn std::error::Error + Send + Sync>> {
-        #[derive(Serialize)]
-        struct AuthForm<'a> {
-            client_id: &'a str,
-            client_secret: &'a str,
-        }
-
-        let form = AuthForm {
-            client_id,
-            client_secret,
-        };
+        let fields = self.form_field_names();
+        let form = credentials_form(&fields, client_id, client_secret);
 
         let token: AuthToken = self
             .client
             .post_form(&self.auth_path, &form, None)
             .await?;
 
-        Ok(token)
+        Ok(token.received_at(self.client.unix_now()))
+    }
+
+    async fn authenticate_detailed(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<LoginResult, AuthError> {
+        let resp = self.send_credentials(client_id, client_secret).await?;
+
+        if resp.status.is_success() {
+            return Ok(LoginResult {
+                status: resp.status,
+                token: Some(resp.decode::<AuthToken>()?.received_at(self.client.unix_now())),
+                body: None,
+            });
+        }
+
+        Ok(LoginResult {
+            status: resp.status,
+            token: None,
+            body: Some(resp.text()),
+        })
     }
+
+    async fn verify(&self, client_id: &str, client_secret: &str) -> Result<bool, AuthError> {
+        let resp = self.send_credentials(client_id, client_secret).await?;
+        if resp.status == StatusCode::UNAUTHORIZED {
+            return Ok(false);
+        }
+
+        let token: AuthToken = self.client.error_for_status(resp)?.decode()?;
+        token.validate()?;
+        Ok(true)
+    }
+}
+
+impl RestAuthRepository {
+    // The token request itself, without status handling.
+    async fn send_credentials(&self, client_id: &str, client_secret: &str) -> Result<ApiResponse, ApiError> {
+        let fields = self.form_field_names();
+        let form = credentials_form(&fields, client_id, client_secret);
+
+        let req = self
+            .client
+            .request_builder(Method::POST, &self.auth_path)?
+            .header(ACCEPT, "application/json");
+        let req = self.client.form_body(req, &form)?;
+        self.client.send(req).await
+    }
+}
+
+fn credentials_form<'a>(
+    (id_field, secret_field): &'a (String, String),
+    client_id: &'a str,
+    client_secret: &'a str,
+) -> [(&'a str, &'a str); 2] {
+    [
+        (id_field.as_str(), client_id),
+        (secret_field.as_str(), client_secret),
+    ]
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(target_arch = "wasm32")))]
 #[path = "auth_repository_tests.rs"]
 mod auth_repository_tests;
@@ -19,6 +19,8 @@ async fn give_valid_credentials_when_authenticate_then_token_should_be_returned(
             expires_in: Some(3600),
             refresh_token: Some("refresh".into()),
             scope: Some("read write".into()),
+            #[cfg(feature = "chrono")]
+            obtained_at: Default::default(),
         });
     });
 
@@ -65,3 +67,143 @@ async fn give_invalid_credentials_when_authenticate_then_error_should_be_propaga
     mock.assert();
     assert!(result.is_err(), "expected authentication failure");
 }
+
+#[tokio::test]
+async fn give_camel_case_naming_when_authenticate_then_form_keys_should_be_camel_case() {
+    let server = MockServer::start();
+    let auth_path = "/auth/login";
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(auth_path)
+            .body_contains("clientId=my_id")
+            .body_contains("clientSecret=my_secret");
+        then.status(200).json_body_obj(&serde_json::json!({
+            "access_token": "abc123",
+            "token_type": "Bearer"
+        }));
+    });
+
+    // give
+    let repo = RestAuthRepository::new(&server.base_url(), auth_path)
+        .with_field_naming(FieldNaming::CamelCase);
+
+    // when
+    let token = repo
+        .authenticate("my_id", "my_secret")
+        .await
+        .expect("token expected");
+
+    // then
+    mock.assert();
+    assert_eq!(token.access_token, "abc123");
+}
+
+#[tokio::test]
+async fn give_custom_form_fields_when_authenticate_then_overridden_keys_should_be_sent() {
+    let server = MockServer::start();
+    let auth_path = "/auth/login";
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(auth_path)
+            .body_contains("username=my_id")
+            .body_contains("password=my_secret");
+        then.status(200).json_body_obj(&serde_json::json!({
+            "access_token": "abc123",
+            "token_type": "Bearer"
+        }));
+    });
+
+    // give
+    let repo = RestAuthRepository::new(&server.base_url(), auth_path)
+        .with_field_naming(FieldNaming::CamelCase)
+        .with_form_fields("username", "password");
+
+    // when
+    let token = repo
+        .authenticate("my_id", "my_secret")
+        .await
+        .expect("token expected");
+
+    // then
+    mock.assert();
+    assert_eq!(token.access_token, "abc123");
+}
+
+#[tokio::test]
+async fn give_form_content_type_with_charset_when_authenticate_then_it_should_be_sent() {
+    let server = MockServer::start();
+    let auth_path = "/auth/login";
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(auth_path)
+            .header("content-type", "application/x-www-form-urlencoded; charset=utf-8")
+            .body_contains("client_id=my_id");
+        then.status(200).json_body_obj(&serde_json::json!({
+            "access_token": "abc123",
+            "token_type": "Bearer"
+        }));
+    });
+
+    // give
+    let repo = RestAuthRepository::new(&server.base_url(), auth_path)
+        .with_form_content_type("application/x-www-form-urlencoded; charset=utf-8");
+
+    // when
+    let token = repo.authenticate("my_id", "my_secret").await.unwrap();
+    let detailed = repo.authenticate_detailed("my_id", "my_secret").await.unwrap();
+
+    // then
+    mock.assert_hits(2);
+    assert_eq!(token.access_token, "abc123");
+    assert!(detailed.token.is_some());
+}
+
+#[cfg(feature = "chrono")]
+#[tokio::test]
+async fn give_client_clock_when_authenticate_then_expires_at_should_count_from_the_clock() {
+    use crate::api::Clock;
+    use std::time::Duration;
+
+    struct WallClock;
+
+    #[async_trait]
+    impl Clock for WallClock {
+        fn now(&self) -> Duration {
+            Duration::ZERO
+        }
+
+        fn unix_now(&self) -> Duration {
+            Duration::from_secs(1_700_000_000)
+        }
+
+        async fn sleep(&self, _duration: Duration) {}
+    }
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/auth/login");
+            then.status(200).json_body(serde_json::json!({
+                "access_token": "abc123",
+                "token_type": "Bearer",
+                "expires_in": 3600
+            }));
+        })
+        .await;
+
+    // give
+    let repo = RestAuthRepository::new(&server.base_url(), "/auth/login")
+        .with_client_options(|client| client.with_clock(WallClock));
+
+    // when
+    let token = repo.authenticate("id", "secret").await.unwrap();
+    let detailed = repo.authenticate_detailed("id", "secret").await.unwrap();
+
+    // then
+    let expected = chrono::DateTime::from_timestamp(1_700_003_600, 0);
+    assert_eq!(token.expires_at(), expected);
+    assert_eq!(detailed.token.unwrap().expires_at(), expected);
+}
@@ -1 +1,2 @@
+pub mod api_key_repository;
 pub mod auth_repository;
\ No newline at end of file
@@ -1,13 +1,34 @@
-use crate::models::AuthToken;
-use crate::repository::auth_repository::AuthRepository;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::api::runtime::unix_now;
+use crate::models::{AuthError, AuthEvent, AuthToken, LoginResult};
+use crate::repository::auth_repository::{AuthRepository, RestAuthRepository};
+
+type EventListener = Arc<dyn Fn(&AuthEvent) + Send + Sync>;
 
 pub struct AuthService<R: AuthRepository> {
     repo: R,
+    listener: Option<EventListener>,
 }
 
 impl<R: AuthRepository> AuthService<R> {
     pub fn new(repo: R) -> Self {
-        Self { repo }
+        Self {
+            repo,
+            listener: None,
+        }
+    }
+
+    // Called with every token issued, refreshed or refused, e.g. to feed
+    // auth metrics. Runs inline, so it should not block.
+    pub fn with_event_listener<F>(mut self, listener: F) -> Self
+    where
+        F: Fn(&AuthEvent) + Send + Sync + 'static,
+    {
+        self.listener = Some(Arc::new(listener));
+        self
     }
 
     pub async fn login(
@@ -15,10 +36,87 @@ impl<R: AuthRepository> AuthService<R> {
         client_id: &str,
         client_secret: &str,
     ) -> Result<AuthToken, Box<dyn std::error::Error + Send + Sync>> {
-        self.repo.authenticate(client_id, client_secret).await
+        let result = self.repo.authenticate(client_id, client_secret).await;
+        self.emit(&result, |at| AuthEvent::TokenIssued { at });
+        result
+    }
+
+    // Logs in again to replace an expired token. Same as login, except that
+    // success is reported as TokenRefreshed.
+    pub async fn refresh(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<AuthToken, Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.repo.authenticate(client_id, client_secret).await;
+        self.emit(&result, |at| AuthEvent::TokenRefreshed { at });
+        result
+    }
+
+    // Like login, but a refused login still returns Ok with the status and
+    // raw body so it can be inspected. Only transport or decoding problems
+    // are errors.
+    pub async fn login_detailed(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<LoginResult, AuthError>
+    where
+        R: Sync,
+    {
+        let result = self
+            .repo
+            .authenticate_detailed(client_id, client_secret)
+            .await;
+        if let Some(listener) = &self.listener {
+            let at = unix_now();
+            listener(&match &result {
+                Ok(LoginResult { token: Some(_), .. }) => AuthEvent::TokenIssued { at },
+                Ok(refused) => AuthEvent::AuthFailed {
+                    at,
+                    reason: format!("login refused: {}", refused.status),
+                },
+                Err(err) => AuthEvent::AuthFailed {
+                    at,
+                    reason: err.to_string(),
+                },
+            });
+        }
+        result
+    }
+
+    // Cheap preflight check before a long operation: Ok(true) when the
+    // credentials are accepted, Ok(false) on a clean 401. Other refusals and
+    // transport problems are errors, mapped like any other client call.
+    pub async fn verify(&self, client_id: &str, client_secret: &str) -> Result<bool, AuthError>
+    where
+        R: Sync,
+    {
+        self.repo.verify(client_id, client_secret).await
+    }
+
+    fn emit<T, E: Display>(&self, result: &Result<T, E>, success: impl FnOnce(Duration) -> AuthEvent) {
+        let Some(listener) = &self.listener else {
+            return;
+        };
+        let at = unix_now();
+        listener(&match result {
+            Ok(_) => success(at),
+            Err(err) => AuthEvent::AuthFailed {
+                at,
+                reason: err.to_string(),
+            },
+        });
+    }
+}
+
+impl AuthService<RestAuthRepository> {
+    // Shorthand for AuthService::new(RestAuthRepository::new(base_url, auth_path)).
+    pub fn rest(base_url: &str, auth_path: &str) -> Self {
+        Self::new(RestAuthRepository::new(base_url, auth_path))
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(target_arch = "wasm32")))]
 #[path = "auth_service_tests.rs"]
 mod auth_service_tests;
@@ -23,6 +23,7 @@ impl MockAuthRepo {
     }
 }
 
+#[allow(clippy::io_other_error)]
 #[async_trait]
 impl AuthRepository for MockAuthRepo {
     async fn authenticate(
@@ -50,6 +51,8 @@ async fn give_valid_credentials_when_login_then_token_should_be_returned() {
         expires_in: Some(3600),
         refresh_token: Some("refresh".into()),
         scope: Some("read write".into()),
+        #[cfg(feature = "chrono")]
+        obtained_at: Default::default(),
     };
 
     // give
@@ -79,3 +82,329 @@ async fn give_invalid_credentials_when_login_then_error_should_be_propagated() {
     // then
     assert!(result.is_err(), "expected error to bubble up");
 }
+
+#[tokio::test]
+async fn give_rest_service_when_login_then_token_should_come_from_the_auth_endpoint() {
+    use httpmock::prelude::*;
+
+    let server = MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/auth/login")
+                .x_www_form_urlencoded_tuple("client_id", "id")
+                .x_www_form_urlencoded_tuple("client_secret", "secret");
+            then.status(200).json_body(serde_json::json!({
+                "access_token": "abc123",
+                "token_type": "Bearer"
+            }));
+        })
+        .await;
+
+    // give
+    let service = AuthService::rest(&server.base_url(), "/auth/login");
+
+    // when
+    let token = service.login("id", "secret").await.unwrap();
+
+    // then
+    mock.assert_async().await;
+    assert_eq!(token.access_token, "abc123");
+}
+
+#[tokio::test]
+async fn give_slow_auth_endpoint_and_timeout_when_login_then_should_fail_with_timeout() {
+    use crate::api::ApiError;
+    use crate::repository::auth_repository::RestAuthRepository;
+    use httpmock::prelude::*;
+    use std::time::Duration;
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/auth/login");
+            then.status(200)
+                .delay(Duration::from_secs(5))
+                .json_body(serde_json::json!({
+                    "access_token": "abc123",
+                    "token_type": "Bearer"
+                }));
+        })
+        .await;
+
+    // give
+    let repo = RestAuthRepository::new(&server.base_url(), "/auth/login")
+        .with_timeout(Duration::from_millis(100));
+    let service = AuthService::new(repo);
+
+    // when
+    let started = std::time::Instant::now();
+    let err = service.login("id", "secret").await.unwrap_err();
+
+    // then
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert!(
+        matches!(err.downcast_ref::<ApiError>(), Some(ApiError::Http(e)) if e.is_timeout()),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn give_accepted_credentials_when_login_detailed_then_status_and_token_should_be_exposed() {
+    use crate::repository::auth_repository::RestAuthRepository;
+    use httpmock::prelude::*;
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/auth/login");
+            then.status(200).json_body(serde_json::json!({
+                "access_token": "abc123",
+                "token_type": "Bearer"
+            }));
+        })
+        .await;
+
+    // give
+    let service = AuthService::new(RestAuthRepository::new(&server.base_url(), "/auth/login"));
+
+    // when
+    let result = service.login_detailed("id", "secret").await.unwrap();
+
+    // then
+    assert_eq!(result.status, reqwest::StatusCode::OK);
+    assert_eq!(result.token.unwrap().access_token, "abc123");
+    assert!(result.body.is_none());
+}
+
+#[tokio::test]
+async fn give_refused_credentials_when_login_detailed_then_status_and_raw_body_should_be_exposed() {
+    use crate::repository::auth_repository::RestAuthRepository;
+    use httpmock::prelude::*;
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/auth/login");
+            then.status(401)
+                .body(r#"{"error":"invalid_client","error_description":"unknown client"}"#);
+        })
+        .await;
+
+    // give
+    let service = AuthService::new(RestAuthRepository::new(&server.base_url(), "/auth/login"));
+
+    // when
+    let result = service.login_detailed("bad", "creds").await.unwrap();
+
+    // then
+    assert_eq!(result.status, reqwest::StatusCode::UNAUTHORIZED);
+    assert!(result.token.is_none());
+    assert_eq!(
+        result.body.as_deref(),
+        Some(r#"{"error":"invalid_client","error_description":"unknown client"}"#)
+    );
+}
+
+#[tokio::test]
+async fn give_repository_without_http_details_when_login_detailed_then_default_should_wrap_error() {
+    // give
+    let repo = MockAuthRepo::new(MockResponse::Failure("invalid".into()));
+    let service = AuthService::new(repo);
+
+    // when
+    let result = service.login_detailed("bad", "creds").await;
+
+    // then
+    assert!(matches!(result, Err(AuthError::Repository(_))));
+}
+
+fn recording_listener() -> (Arc<Mutex<Vec<AuthEvent>>>, impl Fn(&AuthEvent) + Send + Sync + 'static) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    (events, move |event: &AuthEvent| sink.lock().unwrap().push(event.clone()))
+}
+
+#[tokio::test]
+async fn give_event_listener_when_login_then_refresh_then_events_should_fire_in_order() {
+    let token = AuthToken {
+        access_token: "abc123".into(),
+        token_type: "Bearer".into(),
+        expires_in: Some(60),
+        refresh_token: None,
+        scope: None,
+        #[cfg(feature = "chrono")]
+        obtained_at: Default::default(),
+    };
+
+    // give
+    let (events, listener) = recording_listener();
+    let service = AuthService::new(MockAuthRepo::new(MockResponse::Success(token)))
+        .with_event_listener(listener);
+    let (failures, listener) = recording_listener();
+    let failing = AuthService::new(MockAuthRepo::new(MockResponse::Failure("invalid".into())))
+        .with_event_listener(listener);
+
+    // when
+    service.login("id", "secret").await.unwrap();
+    service.refresh("id", "secret").await.unwrap();
+    failing.login("bad", "creds").await.unwrap_err();
+
+    // then
+    let events = events.lock().unwrap();
+    assert!(matches!(
+        events.as_slice(),
+        [AuthEvent::TokenIssued { .. }, AuthEvent::TokenRefreshed { .. }]
+    ));
+    assert!(events[0].at() <= events[1].at());
+    assert!(events[0].at() > std::time::Duration::ZERO);
+    assert!(matches!(
+        failures.lock().unwrap().as_slice(),
+        [AuthEvent::AuthFailed { reason, .. }] if reason == "invalid"
+    ));
+}
+
+async fn verify_against(status: u16) -> Result<bool, AuthError> {
+    use crate::repository::auth_repository::RestAuthRepository;
+    use httpmock::prelude::*;
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/auth/login");
+            then.status(status).json_body(serde_json::json!({
+                "access_token": "abc123",
+                "token_type": "Bearer"
+            }));
+        })
+        .await;
+
+    let service = AuthService::new(RestAuthRepository::new(&server.base_url(), "/auth/login"));
+    service.verify("id", "secret").await
+}
+
+#[tokio::test]
+async fn give_accepted_credentials_when_verify_then_should_return_true() {
+    // give / when
+    let result = verify_against(200).await;
+
+    // then
+    assert!(result.unwrap());
+}
+
+#[tokio::test]
+async fn give_unauthorized_credentials_when_verify_then_should_return_false() {
+    // give / when
+    let result = verify_against(401).await;
+
+    // then
+    assert!(!result.unwrap());
+}
+
+#[tokio::test]
+async fn give_server_error_when_verify_then_should_return_error() {
+    use crate::api::ApiError;
+
+    // give / when
+    let result = verify_against(500).await;
+
+    // then
+    assert!(
+        matches!(
+            result,
+            Err(AuthError::Api(ApiError::Status { status, .. }))
+                if status == reqwest::StatusCode::INTERNAL_SERVER_ERROR
+        ),
+        "unexpected result: {result:?}"
+    );
+}
+
+#[tokio::test]
+async fn give_forbidden_or_rate_limited_login_when_verify_then_crate_error_variants_should_be_returned() {
+    use crate::api::ApiError;
+    use crate::repository::auth_repository::RestAuthRepository;
+    use httpmock::prelude::*;
+    use std::time::Duration;
+
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/forbidden/login");
+            then.status(403).body("client disabled");
+        })
+        .await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/busy/login");
+            then.status(429).header("Retry-After", "5");
+        })
+        .await;
+
+    // give
+    let forbidden = AuthService::new(RestAuthRepository::new(&server.base_url(), "/forbidden/login"));
+    let busy = AuthService::new(RestAuthRepository::new(&server.base_url(), "/busy/login"));
+
+    // when
+    let forbidden = forbidden.verify("id", "secret").await;
+    let busy = busy.verify("id", "secret").await;
+
+    // then
+    assert!(
+        matches!(&forbidden, Err(AuthError::Api(ApiError::Forbidden { body, .. })) if body == "client disabled"),
+        "unexpected result: {forbidden:?}"
+    );
+    assert!(
+        matches!(
+            busy,
+            Err(AuthError::Api(ApiError::RateLimited { retry_after: Some(delay) }))
+                if delay == Duration::from_secs(5)
+        ),
+        "unexpected result: {busy:?}"
+    );
+}
+
+struct RefusingRepo(reqwest::StatusCode);
+
+#[async_trait]
+impl AuthRepository for RefusingRepo {
+    async fn authenticate(
+        &self,
+        _client_id: &str,
+        _client_secret: &str,
+    ) -> Result<AuthToken, Box<dyn std::error::Error + Send + Sync>> {
+        Err("refused".into())
+    }
+
+    async fn authenticate_detailed(
+        &self,
+        _client_id: &str,
+        _client_secret: &str,
+    ) -> Result<LoginResult, AuthError> {
+        Ok(LoginResult {
+            status: self.0,
+            token: None,
+            body: Some("nope".into()),
+        })
+    }
+}
+
+#[tokio::test]
+async fn give_custom_repository_refusal_when_verify_then_default_should_map_like_the_client() {
+    use crate::api::ApiError;
+    use reqwest::StatusCode;
+
+    // give
+    let unauthorized = AuthService::new(RefusingRepo(StatusCode::UNAUTHORIZED));
+    let forbidden = AuthService::new(RefusingRepo(StatusCode::FORBIDDEN));
+    let conflict = AuthService::new(RefusingRepo(StatusCode::PRECONDITION_FAILED));
+
+    // when
+    let unauthorized = unauthorized.verify("id", "secret").await;
+    let forbidden = forbidden.verify("id", "secret").await;
+    let conflict = conflict.verify("id", "secret").await;
+
+    // then
+    assert!(!unauthorized.unwrap());
+    assert!(matches!(forbidden, Err(AuthError::Api(ApiError::Forbidden { body, .. })) if body == "nope"));
+    assert!(matches!(conflict, Err(AuthError::Api(ApiError::PreconditionFailed { .. }))));
+}
@@ -0,0 +1,177 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::sync::Mutex;
+
+use crate::api::{ApiClient, ApiError, Clock, HeaderPairs, SystemClock};
+use crate::models::{AuthError, AuthToken};
+use crate::repository::auth_repository::AuthRepository;
+use crate::service::auth_service::AuthService;
+
+// How long before its expires_in a cached token is replaced, so a token
+// does not expire in flight or on a server whose clock runs ahead.
+const DEFAULT_EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+struct CachedToken {
+    access_token: String,
+    // Clock time after which the token is no longer sent (expiry margin
+    // already taken off); None when the server gave no expires_in.
+    expires_at: Option<Duration>,
+}
+
+// An ApiClient that logs in on first use and sends the resulting token with
+// every request. The token is cached and fetched again shortly before its
+// expires_in has passed, or when a request comes back 401.
+pub struct AuthenticatedClient<R: AuthRepository> {
+    auth: AuthService<R>,
+    client: ApiClient,
+    client_id: String,
+    client_secret: String,
+    clock: Arc<dyn Clock>,
+    expiry_margin: Duration,
+    token: Mutex<Option<CachedToken>>,
+}
+
+impl<R: AuthRepository> AuthenticatedClient<R> {
+    pub fn new(
+        auth: AuthService<R>,
+        base_url: impl Into<String>,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Self {
+        Self {
+            auth,
+            client: ApiClient::new(base_url),
+            client_id: client_id.to_string(),
+            client_secret: client_secret.to_string(),
+            clock: Arc::new(SystemClock),
+            expiry_margin: DEFAULT_EXPIRY_MARGIN,
+            token: Mutex::new(None),
+        }
+    }
+
+    // Replaces the underlying client, e.g. to add retries or default headers.
+    pub fn with_client(mut self, client: ApiClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    // Time source for token expiry.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    // Replaces the 30 second margin taken off expires_in before a token is
+    // considered expired.
+    pub fn with_expiry_margin(mut self, margin: Duration) -> Self {
+        self.expiry_margin = margin;
+        self
+    }
+
+    // The cached access token, logging in first when there is none or it has
+    // expired. Concurrent callers wait for a single login.
+    pub async fn access_token(&self) -> Result<String, AuthError> {
+        let mut cached = self.token.lock().await;
+        let now = self.clock.now();
+        if let Some(token) = cached.as_ref()
+            && token.expires_at.is_none_or(|at| now < at)
+        {
+            return Ok(token.access_token.clone());
+        }
+
+        let token: AuthToken = match cached.as_ref() {
+            Some(_) => self.auth.refresh(&self.client_id, &self.client_secret).await,
+            None => self.auth.login(&self.client_id, &self.client_secret).await,
+        }
+        .map_err(AuthError::Repository)?;
+        token.validate()?;
+        self.check_token_type(&token)?;
+
+        let access_token = token.access_token.clone();
+        *cached = Some(CachedToken {
+            access_token: token.access_token,
+            expires_at: token
+                .expires_in
+                .map(|secs| now + Duration::from_secs(secs).saturating_sub(self.expiry_margin)),
+        });
+        Ok(access_token)
+    }
+
+    // The client sends the token as `<scheme> <token>`, or bare, which only
+    // works for bearer tokens. DPoP and MAC tokens need a proof or signature
+    // it cannot produce, so any other token_type is refused up front.
+    fn check_token_type(&self, token: &AuthToken) -> Result<(), AuthError> {
+        let scheme = self.client.token_scheme().unwrap_or("Bearer");
+        if !token.token_type.trim().eq_ignore_ascii_case(scheme) {
+            return Err(AuthError::UnsupportedTokenType {
+                token_type: token.token_type.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    // Forgets `access_token` unless another caller already replaced it, so
+    // the next access_token() refreshes it.
+    async fn invalidate(&self, access_token: &str) {
+        let mut cached = self.token.lock().await;
+        if let Some(token) = cached.as_mut()
+            && token.access_token == access_token
+        {
+            token.expires_at = Some(Duration::ZERO);
+        }
+    }
+
+    // Runs `call` with the current token. A 401 means the server no longer
+    // accepts it (revoked, or expired early), so the token is refreshed and
+    // the call made once more; a second 401 is returned as is.
+    async fn with_token<T, F, Fut>(&self, call: F) -> Result<T, AuthError>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<T, ApiError>>,
+    {
+        let token = self.access_token().await?;
+        match call(token.clone()).await {
+            Err(ApiError::Unauthorized { .. }) => {
+                self.invalidate(&token).await;
+                let token = self.access_token().await?;
+                Ok(call(token).await?)
+            }
+            result => Ok(result?),
+        }
+    }
+
+    pub async fn get_json<TResp>(
+        &self,
+        path: &str,
+        extra_headers: Option<&dyn HeaderPairs>,
+    ) -> Result<TResp, AuthError>
+    where
+        TResp: DeserializeOwned,
+    {
+        self.with_token(|token| async move {
+            self.client.get_json_as(path, &token, extra_headers).await
+        })
+        .await
+    }
+
+    pub async fn post_json<TBody, TResp>(
+        &self,
+        path: &str,
+        body: &TBody,
+        extra_headers: Option<&dyn HeaderPairs>,
+    ) -> Result<TResp, AuthError>
+    where
+        TBody: Serialize + ?Sized,
+        TResp: DeserializeOwned,
+    {
+        self.with_token(|token| async move {
+            self.client
+                .post_json_as(path, &token, body, extra_headers)
+                .await
+        })
+        .await
+    }
+}
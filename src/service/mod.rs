@@ -1 +1,2 @@
-pub mod auth_service;
\ No newline at end of file
+pub mod auth_service;
+pub mod authenticated_client;
@@ -0,0 +1,27 @@
+use httpmock::MockServer;
+use reqwest::Method;
+use serde::Serialize;
+
+use crate::api::ApiClient;
+
+// Starts a mock server that answers `method path` with `status` and `body`
+// as JSON, plus a client pointed at it. Keep the server alive for as long as
+// the client is used; more mocks can be added to it with `server.mock`.
+pub fn mock_json<TBody>(method: Method, path: &str, status: u16, body: &TBody) -> (MockServer, ApiClient)
+where
+    TBody: Serialize + ?Sized,
+{
+    let server = MockServer::start();
+    let body = serde_json::to_value(body).expect("mock body must serialize to JSON");
+    server.mock(|when, then| {
+        when.method(method.as_str()).path(path);
+        then.status(status).json_body(body);
+    });
+
+    let client = ApiClient::new(server.base_url());
+    (server, client)
+}
+
+#[cfg(test)]
+#[path = "testkit_tests.rs"]
+mod testkit_tests;
@@ -0,0 +1,50 @@
+use serde::Deserialize;
+
+use super::*;
+use crate::api::ApiError;
+
+#[derive(Debug, Deserialize)]
+struct User {
+    name: String,
+}
+
+#[tokio::test]
+async fn give_mock_json_when_get_json_then_client_should_decode_body() {
+    // give
+    let (_server, client) = mock_json(Method::GET, "/users/1", 200, &serde_json::json!({ "name": "ada" }));
+
+    // when
+    let user: User = client.get_json("/users/1", None).await.unwrap();
+
+    // then
+    assert_eq!(user.name, "ada");
+}
+
+#[tokio::test]
+async fn give_mock_json_with_error_status_when_post_json_then_should_return_status_error() {
+    // give
+    let (_server, client) = mock_json(Method::POST, "/users", 422, &serde_json::json!({ "error": "taken" }));
+
+    // when
+    let result = client
+        .post_json::<_, User>("/users", &serde_json::json!({ "name": "ada" }), None)
+        .await;
+
+    // then
+    assert!(matches!(
+        result,
+        Err(ApiError::Status { status, body, .. }) if status == 422 && body.contains("taken")
+    ));
+}
+
+#[tokio::test]
+async fn give_mock_json_when_other_path_requested_then_server_should_return_404() {
+    // give
+    let (_server, client) = mock_json(Method::GET, "/users/1", 200, &serde_json::json!({ "name": "ada" }));
+
+    // when
+    let result = client.get_json::<User>("/users/2", None).await;
+
+    // then
+    assert!(matches!(result, Err(ApiError::Status { status, .. }) if status == 404));
+}
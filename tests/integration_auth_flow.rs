@@ -1,8 +1,16 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
 use httpmock::prelude::*;
-use rust_api_client::api::ApiClient;
-use rust_api_client::models::AuthToken;
-use rust_api_client::repository::auth_repository::RestAuthRepository;
+use rust_api_client::api::{ApiClient, ApiError, Clock};
+use rust_api_client::models::{AuthError, AuthEvent, AuthToken};
+use rust_api_client::repository::auth_repository::{AuthRepository, RestAuthRepository};
 use rust_api_client::service::auth_service::AuthService;
+use rust_api_client::service::authenticated_client::AuthenticatedClient;
 use serde::{Deserialize, Serialize};
 
 #[tokio::test]
@@ -22,6 +30,8 @@ async fn give_valid_credentials_when_login_e2e_then_token_should_be_returned() {
             expires_in: Some(3600),
             refresh_token: Some("refresh".into()),
             scope: Some("read write".into()),
+            #[cfg(feature = "chrono")]
+            obtained_at: Default::default(),
         });
     });
 
@@ -153,3 +163,290 @@ async fn give_crud_endpoints_when_calling_api_client_e2e_then_responses_should_m
     );
     assert_eq!(deleted["deleted"], serde_json::json!(true));
 }
+
+// Only moves when the test advances it.
+#[derive(Clone, Default)]
+struct ManualClock(Arc<Mutex<Duration>>);
+
+impl ManualClock {
+    fn advance(&self, by: Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+#[async_trait::async_trait]
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.0.lock().unwrap()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+fn login_mock(server: &MockServer, expires_in: Option<u64>) -> httpmock::Mock<'_> {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/auth/login")
+            .body_contains("client_id=my_id")
+            .body_contains("client_secret=my_secret");
+        then.status(200).json_body_obj(&AuthToken {
+            access_token: "abc123".into(),
+            token_type: "Bearer".into(),
+            expires_in,
+            refresh_token: None,
+            scope: None,
+            #[cfg(feature = "chrono")]
+            obtained_at: Default::default(),
+        });
+    })
+}
+
+fn authenticated_client(server: &MockServer) -> AuthenticatedClient<RestAuthRepository> {
+    let service = AuthService::rest(&server.base_url(), "/auth/login");
+    AuthenticatedClient::new(service, server.base_url(), "my_id", "my_secret")
+}
+
+#[tokio::test]
+async fn give_no_token_when_authenticated_client_first_call_then_should_login_and_send_token() {
+    let server = MockServer::start();
+    let login = login_mock(&server, Some(3600));
+    let items = server.mock(|when, then| {
+        when.method(GET)
+            .path("/items")
+            .header("Authorization", "Bearer abc123");
+        then.status(200).json_body(serde_json::json!(["one"]));
+    });
+
+    // give
+    let client = authenticated_client(&server);
+
+    // when
+    let fetched: Vec<String> = client.get_json("/items", None).await.unwrap();
+
+    // then
+    login.assert();
+    items.assert();
+    assert_eq!(fetched, ["one"]);
+}
+
+#[tokio::test]
+async fn give_cached_token_when_authenticated_client_calls_again_then_should_not_login_again() {
+    let server = MockServer::start();
+    let login = login_mock(&server, Some(3600));
+    let items = server.mock(|when, then| {
+        when.method(POST)
+            .path("/items")
+            .header("Authorization", "Bearer abc123");
+        then.status(201).json_body(serde_json::json!({ "id": 1 }));
+    });
+
+    // give
+    let clock = ManualClock::default();
+    let client = authenticated_client(&server).with_clock(clock.clone());
+
+    // when
+    for _ in 0..3 {
+        let _: serde_json::Value = client
+            .post_json("/items", &serde_json::json!({ "name": "one" }), None)
+            .await
+            .unwrap();
+        clock.advance(Duration::from_secs(60));
+    }
+
+    // then
+    login.assert_hits(1);
+    items.assert_hits(3);
+}
+
+#[tokio::test]
+async fn give_expired_token_when_authenticated_client_calls_then_should_login_again() {
+    let server = MockServer::start();
+    let login = login_mock(&server, Some(60));
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/items")
+            .header("Authorization", "Bearer abc123");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+
+    // give
+    let clock = ManualClock::default();
+    let client = authenticated_client(&server).with_clock(clock.clone());
+    let _: Vec<String> = client.get_json("/items", None).await.unwrap();
+
+    // when
+    clock.advance(Duration::from_secs(61));
+    let _: Vec<String> = client.get_json("/items", None).await.unwrap();
+
+    // then
+    login.assert_hits(2);
+}
+
+#[tokio::test]
+async fn give_event_listener_when_authenticated_client_token_expires_then_issued_then_refreshed_should_fire() {
+    let server = MockServer::start();
+    login_mock(&server, Some(60));
+    server.mock(|when, then| {
+        when.method(GET).path("/items");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+
+    // give
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let service = AuthService::rest(&server.base_url(), "/auth/login")
+        .with_event_listener(move |event| sink.lock().unwrap().push(event.clone()));
+    let clock = ManualClock::default();
+    let client = AuthenticatedClient::new(service, server.base_url(), "my_id", "my_secret")
+        .with_clock(clock.clone());
+
+    // when
+    let _: Vec<String> = client.get_json("/items", None).await.unwrap();
+    clock.advance(Duration::from_secs(61));
+    let _: Vec<String> = client.get_json("/items", None).await.unwrap();
+
+    // then
+    assert!(matches!(
+        events.lock().unwrap().as_slice(),
+        [AuthEvent::TokenIssued { .. }, AuthEvent::TokenRefreshed { .. }]
+    ));
+}
+
+#[tokio::test]
+async fn give_token_close_to_expiry_when_authenticated_client_calls_then_should_login_again_before_it_expires() {
+    let server = MockServer::start();
+    let login = login_mock(&server, Some(60));
+    server.mock(|when, then| {
+        when.method(GET).path("/items");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+
+    // give
+    let clock = ManualClock::default();
+    let client = authenticated_client(&server).with_clock(clock.clone());
+    let _: Vec<String> = client.get_json("/items", None).await.unwrap();
+
+    // when
+    clock.advance(Duration::from_secs(29));
+    let _: Vec<String> = client.get_json("/items", None).await.unwrap();
+    clock.advance(Duration::from_secs(2));
+    let _: Vec<String> = client.get_json("/items", None).await.unwrap();
+
+    // then
+    login.assert_hits(2);
+}
+
+// Hands out "token-1", "token-2"... so a test can tell a refreshed token from
+// the first one.
+#[derive(Default)]
+struct CountingRepo(AtomicUsize);
+
+#[async_trait]
+impl AuthRepository for CountingRepo {
+    async fn authenticate(
+        &self,
+        _client_id: &str,
+        _client_secret: &str,
+    ) -> Result<AuthToken, Box<dyn std::error::Error + Send + Sync>> {
+        let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(AuthToken {
+            access_token: format!("token-{n}"),
+            token_type: "Bearer".into(),
+            expires_in: Some(3600),
+            refresh_token: None,
+            scope: None,
+            #[cfg(feature = "chrono")]
+            obtained_at: Default::default(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn give_revoked_token_when_authenticated_client_gets_401_then_should_refresh_and_retry_once() {
+    let server = MockServer::start();
+    let revoked = server.mock(|when, then| {
+        when.method(GET)
+            .path("/items")
+            .header("Authorization", "Bearer token-1");
+        then.status(401);
+    });
+    let items = server.mock(|when, then| {
+        when.method(GET)
+            .path("/items")
+            .header("Authorization", "Bearer token-2");
+        then.status(200).json_body(serde_json::json!(["one"]));
+    });
+
+    // give
+    let client = AuthenticatedClient::new(
+        AuthService::new(CountingRepo::default()),
+        server.base_url(),
+        "my_id",
+        "my_secret",
+    );
+
+    // when
+    let fetched: Vec<String> = client.get_json("/items", None).await.unwrap();
+    let again: Vec<String> = client.get_json("/items", None).await.unwrap();
+
+    // then
+    revoked.assert_hits(1);
+    items.assert_hits(2);
+    assert_eq!(fetched, ["one"]);
+    assert_eq!(again, ["one"]);
+}
+
+#[tokio::test]
+async fn give_endpoint_that_always_refuses_when_authenticated_client_calls_then_unauthorized_should_be_returned_after_one_retry() {
+    let server = MockServer::start();
+    let login = login_mock(&server, Some(3600));
+    let items = server.mock(|when, then| {
+        when.method(GET).path("/items");
+        then.status(401).body("nope");
+    });
+
+    // give
+    let client = authenticated_client(&server);
+
+    // when
+    let result = client.get_json::<Vec<String>>("/items", None).await;
+
+    // then
+    login.assert_hits(2);
+    items.assert_hits(2);
+    assert!(
+        matches!(&result, Err(AuthError::Api(ApiError::Unauthorized { body, .. })) if body == "nope"),
+        "unexpected result: {result:?}"
+    );
+}
+
+#[tokio::test]
+async fn give_token_type_the_client_cannot_send_when_authenticated_client_calls_then_unsupported_token_type_should_be_returned() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/auth/login");
+        then.status(200).json_body(serde_json::json!({
+            "access_token": "abc123",
+            "token_type": "DPoP"
+        }));
+    });
+    let items = server.mock(|when, then| {
+        when.method(GET).path("/items");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+
+    // give
+    let client = authenticated_client(&server);
+
+    // when
+    let result = client.get_json::<Vec<String>>("/items", None).await;
+
+    // then
+    items.assert_hits(0);
+    assert!(
+        matches!(&result, Err(AuthError::UnsupportedTokenType { token_type }) if token_type == "DPoP"),
+        "unexpected result: {result:?}"
+    );
+}
@@ -0,0 +1,46 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+// Built once per TLS backend:
+//   cargo test --test tls_backend
+//   cargo test --test tls_backend --no-default-features --features native-tls
+use httpmock::prelude::*;
+use rust_api_client::api::ApiClient;
+
+#[tokio::test]
+async fn give_selected_tls_backend_when_get_json_then_plain_request_should_succeed() {
+    let server = MockServer::start();
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/ping");
+        then.status(200).json_body(serde_json::json!({ "pong": true }));
+    });
+
+    // give
+    let client = ApiClient::new(server.base_url());
+
+    // when
+    let resp: serde_json::Value = client.get_json("/ping", None).await.unwrap();
+
+    // then
+    mock.assert();
+    assert_eq!(resp["pong"], serde_json::json!(true));
+}
+
+#[cfg(feature = "rustls-tls")]
+#[test]
+fn give_rustls_feature_when_building_then_rustls_backend_should_be_linked() {
+    // give / when
+    let client = reqwest::Client::builder().use_rustls_tls().build();
+
+    // then
+    assert!(client.is_ok());
+}
+
+#[cfg(feature = "native-tls")]
+#[test]
+fn give_native_tls_feature_when_building_then_native_backend_should_be_linked() {
+    // give / when
+    let client = reqwest::Client::builder().use_native_tls().build();
+
+    // then
+    assert!(client.is_ok());
+}
@@ -0,0 +1,84 @@
+#![cfg(target_arch = "wasm32")]
+
+use rust_api_client::api::ApiClient;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+// httpmock does not run inside wasm, so the tests start a throwaway Node HTTP
+// server that echoes the method, path and body of the single request it gets.
+#[wasm_bindgen(inline_js = r#"
+export function startEchoServer() {
+    const http = globalThis.process.getBuiltinModule('node:http');
+    return new Promise((resolve) => {
+        const server = http.createServer((req, res) => {
+            let body = '';
+            req.on('data', (chunk) => { body += chunk; });
+            req.on('end', () => {
+                res.setHeader('content-type', 'application/json');
+                res.setHeader('connection', 'close');
+                res.end(JSON.stringify({ method: req.method, path: req.url, body }));
+                server.close();
+            });
+        });
+        server.listen(0, '127.0.0.1', () => {
+            resolve(`http://127.0.0.1:${server.address().port}`);
+        });
+    });
+}
+"#)]
+extern "C" {
+    #[wasm_bindgen(js_name = startEchoServer)]
+    fn start_echo_server() -> js_sys::Promise;
+}
+
+#[derive(Debug, Deserialize)]
+struct Echo {
+    method: String,
+    path: String,
+    body: String,
+}
+
+async fn echo_server_url() -> String {
+    JsFuture::from(start_echo_server())
+        .await
+        .unwrap()
+        .as_string()
+        .unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn give_fetch_backend_when_get_json_then_response_should_be_parsed() {
+    // give
+    let client = ApiClient::new(echo_server_url().await);
+
+    // when
+    let echo: Echo = client.get_json("/items/1", None).await.unwrap();
+
+    // then
+    assert_eq!(echo.method, "GET");
+    assert_eq!(echo.path, "/items/1");
+}
+
+#[wasm_bindgen_test]
+async fn give_fetch_backend_when_post_json_then_body_should_be_sent() {
+    #[derive(Serialize)]
+    struct NewItem<'a> {
+        name: &'a str,
+    }
+
+    // give
+    let client = ApiClient::new(echo_server_url().await);
+
+    // when
+    let echo: Echo = client
+        .post_json("/items", &NewItem { name: "foo" }, None)
+        .await
+        .unwrap();
+
+    // then
+    assert_eq!(echo.method, "POST");
+    assert_eq!(echo.path, "/items");
+    assert_eq!(echo.body, r#"{"name":"foo"}"#);
+}